@@ -0,0 +1,95 @@
+//! A small bot for `snake_game bench --program`: it reads the game's JSON
+//! lines from stdin and answers every state with a move towards the food
+//! that does not run into a wall or a snake.
+//!
+//! `--stall` never answers and `--garbage` answers nonsense, to see how the
+//! game deals with broken bots.
+
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+const DIRECTIONS: [(&str, i64, i64); 4] = [
+    ("Up", -1, 0),
+    ("Down", 1, 0),
+    ("Left", 0, -1),
+    ("Right", 0, 1),
+];
+
+fn point(value: &Value) -> (i64, i64) {
+    (
+        value["x"].as_i64().unwrap_or(0),
+        value["y"].as_i64().unwrap_or(0),
+    )
+}
+
+/// Steps between two cells of a board whose edges wrap around.
+fn distance(a: (i64, i64), b: (i64, i64), size: i64) -> i64 {
+    let dx = (a.0 - b.0).rem_euclid(size);
+    let dy = (a.1 - b.1).rem_euclid(size);
+
+    dx.min(size - dx) + dy.min(size - dy)
+}
+
+fn decide(state: &Value, you: usize, size: i64, walls: &[(i64, i64)]) -> Option<&'static str> {
+    let snakes = state["snakes"].as_array()?;
+    let head = point(snakes.get(you)?["body"].get(0)?);
+    let food = point(&state["food"]);
+
+    let blocked: Vec<(i64, i64)> = snakes
+        .iter()
+        .filter(|snake| snake["alive"].as_bool() == Some(true))
+        .flat_map(|snake| snake["body"].as_array().into_iter().flatten().map(point))
+        .chain(walls.iter().copied())
+        .collect();
+
+    DIRECTIONS
+        .iter()
+        .map(|(name, dx, dy)| {
+            (
+                *name,
+                (
+                    (head.0 + dx).rem_euclid(size),
+                    (head.1 + dy).rem_euclid(size),
+                ),
+            )
+        })
+        .filter(|(_, next)| !blocked.contains(next))
+        .min_by_key(|(_, next)| distance(*next, food, size))
+        .map(|(name, _)| name)
+}
+
+fn main() -> io::Result<()> {
+    let mode = std::env::args().nth(1).unwrap_or_default();
+    let mut stdout = io::stdout().lock();
+    let (mut you, mut size, mut walls) = (0, 1, Vec::new());
+
+    for line in io::stdin().lock().lines() {
+        let message: Value = serde_json::from_str(&line?).unwrap_or(Value::Null);
+
+        match message["type"].as_str() {
+            Some("start") => {
+                you = message["you"].as_u64().unwrap_or(0) as usize;
+                size = message["board"]["table_size"].as_i64().unwrap_or(1);
+                walls = message["board"]["walls"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(point)
+                    .collect();
+            }
+            Some("state") => {
+                let reply = match mode.as_str() {
+                    "--stall" => continue,
+                    "--garbage" => "go left!".to_string(),
+                    _ => json!({ "direction": decide(&message, you, size, &walls) }).to_string(),
+                };
+                writeln!(stdout, "{reply}")?;
+                stdout.flush()?;
+            }
+            Some("forfeit") => eprintln!("{}", message["reason"]),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
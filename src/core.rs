@@ -1,12 +1,45 @@
 mod board;
 mod boards;
+mod bot;
+mod campaign;
+mod death;
 mod direction;
+mod entity;
+mod env;
+mod evolution;
+mod food;
 mod game;
+mod mode;
+mod player;
 mod point;
+mod power_up;
+mod replay;
+mod rewind;
+mod save;
 mod scoreboard;
+mod simulation;
+mod snapshot;
+mod tile;
 
-pub use board::{Board, Wall};
+pub use board::{Board, Spawn, Wall};
 pub use boards::Boards;
+pub use bot::{Bot, Controller, Cycle, Difficulty, Genome, Grid, Hamiltonian, Neural, GENOME_PATH};
+pub use campaign::{Campaign, Level, Progress, Target};
+pub use death::{Death, DeathCause};
 pub use direction::Direction;
+pub use entity::{Entity, Motion, Mover};
+pub use env::{Action, Config, Observation, Reward, Step, VecEnv, View};
+pub use evolution::{Trainer, Training};
+pub use food::{Food, FoodKind};
 pub use game::Game;
-pub use scoreboard::Scoreboard;
+pub use mode::GameMode;
+pub use player::Player;
+pub use point::Point;
+pub use power_up::{Effect, PowerUp, PowerUpKind};
+pub use replay::Replay;
+pub use rewind::Rewind;
+pub use save::SavedGame;
+pub use scoreboard::{Ranking, Record, Scoreboard};
+pub use simulation::Simulation;
+pub use snapshot::Snapshot;
+pub use tile::{PlacedTile, Tile, PORTAL_PAIRS};
@@ -1,15 +1,57 @@
-use super::point::Point;
+use super::{
+    food::FoodRates, point::Point, Direction, Entity, Motion, PlacedTile, Tile, PORTAL_PAIRS,
+};
 
 pub type Wall = Point;
 type Walls = Vec<Wall>;
 
 use serde::{Deserialize, Serialize};
 
+/// Where a player's snake starts: the middle of its body and where it heads.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Spawn {
+    point: Point,
+    direction: Direction,
+}
+
+impl Spawn {
+    pub fn new(point: Point, direction: Direction) -> Self {
+        Self { point, direction }
+    }
+
+    pub fn get_point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Board {
     name: String,
     table_size: u16,
     walls: Walls,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    spawns: Vec<Spawn>,
+    /// Every tile that is neither floor nor a plain wall; boards from before
+    /// tiles only have walls.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tiles: Vec<PlacedTile>,
+    /// Moving obstacles and enemies, where they start and how they move.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    entities: Vec<Entity>,
+    /// How often bonus, poison and golden food turn up besides the normal one.
+    #[serde(default, skip_serializing_if = "FoodRates::is_none")]
+    food: FoodRates,
+    /// The chance of a power-up turning up on a walk.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    power_ups: f64,
+}
+
+fn is_zero(rate: &f64) -> bool {
+    *rate <= 0.0
 }
 
 impl Board {
@@ -29,14 +71,18 @@ impl Board {
             name,
             table_size,
             walls,
+            spawns: Vec::new(),
+            tiles: Vec::new(),
+            entities: Vec::new(),
+            food: FoodRates::default(),
+            power_ups: 0.0,
         }
     }
 
     pub fn copy_with_new_name(&self, name: String) -> Self {
         Self {
             name,
-            table_size: self.table_size,
-            walls: self.walls.clone(),
+            ..self.clone()
         }
     }
 
@@ -45,6 +91,11 @@ impl Board {
             name: "test board".to_string(),
             table_size: 10,
             walls: Vec::from([Wall::new(5, 5)]),
+            spawns: Vec::new(),
+            tiles: Vec::new(),
+            entities: Vec::new(),
+            food: FoodRates::default(),
+            power_ups: 0.0,
         }
     }
 
@@ -52,8 +103,96 @@ impl Board {
         self.table_size
     }
 
+    /// Whether `point` is a wall, breakable or not.
     pub fn is_wall(&self, point: &Wall) -> bool {
-        self.walls.contains(point)
+        self.walls.contains(point) || self.get_tile(point) == Tile::Breakable
+    }
+
+    pub fn get_tile(&self, point: &Point) -> Tile {
+        if self.walls.contains(point) {
+            return Tile::Wall;
+        }
+
+        self.tiles
+            .iter()
+            .find(|placed| placed.get_point() == point)
+            .map_or(Tile::Floor, |placed| placed.get_tile())
+    }
+
+    /// Whether a snake can fill the cell at `point`: it is neither a wall
+    /// nor a portal.
+    pub fn is_open(&self, point: &Point) -> bool {
+        !self.is_wall(point) && !matches!(self.get_tile(point), Tile::Portal(_))
+    }
+
+    /// The other end of the portal at `point`, if it is a portal with one.
+    pub fn get_partner(&self, point: &Point) -> Option<&Point> {
+        let Tile::Portal(pair) = self.get_tile(point) else {
+            return None;
+        };
+
+        self.tiles
+            .iter()
+            .find(|placed| placed.get_tile() == Tile::Portal(pair) && placed.get_point() != point)
+            .map(|placed| placed.get_point())
+    }
+
+    /// Where a head entering `point` heading `direction` ends up: past the
+    /// other end when `point` is a portal, or else at `point` itself.
+    pub fn pass(&self, point: Point, direction: Direction) -> Point {
+        match self.get_partner(&point) {
+            Some(partner) => partner.get_neighbor(&direction, self.table_size),
+            None => point,
+        }
+    }
+
+    /// The pair a new portal belongs to: the first one still missing an end,
+    /// or else the first one not used yet. `None` once every pair is used.
+    pub fn next_portal_pair(&self) -> Option<u8> {
+        let ends = |pair: u8| {
+            self.tiles
+                .iter()
+                .filter(|placed| placed.get_tile() == Tile::Portal(pair))
+                .count()
+        };
+
+        (0..PORTAL_PAIRS)
+            .find(|pair| ends(*pair) == 1)
+            .or_else(|| (0..PORTAL_PAIRS).find(|pair| ends(*pair) == 0))
+    }
+
+    /// The tiles other than floor and plain walls.
+    pub fn get_tiles(&self) -> &[PlacedTile] {
+        &self.tiles
+    }
+
+    /// Makes the cell at `point` into `tile`, whatever it was before.
+    pub fn set_tile(&mut self, point: Point, tile: Tile) -> Result<(), String> {
+        let valid_range = 0..self.table_size as i16;
+        if !valid_range.contains(&point.get_x()) || !valid_range.contains(&point.get_y()) {
+            return Err("the point out of range".to_string());
+        }
+
+        self.remove_wall(&point);
+        self.tiles.retain(|placed| placed.get_point() != &point);
+        match tile {
+            Tile::Floor => Ok(()),
+            Tile::Wall => self.add_wall(point),
+            tile => {
+                self.tiles.push(PlacedTile::new(point, tile));
+                Ok(())
+            }
+        }
+    }
+
+    /// How many cells of the board a snake can fill.
+    pub fn count_open_cells(&self) -> usize {
+        let size = self.table_size as i16;
+
+        (0..size)
+            .flat_map(|x| (0..size).map(move |y| Wall::new(x, y)))
+            .filter(|point| self.is_open(point))
+            .count()
     }
 
     pub fn add_wall(&mut self, point: Wall) -> Result<(), String> {
@@ -76,6 +215,11 @@ impl Board {
         let mut result = vec![vec!["  ".to_string(); len]; len];
 
         Self::put_walls(&mut result, &self.walls);
+        for placed in &self.tiles {
+            let point = placed.get_point();
+            result[(point.get_x() + 1) as usize][(point.get_y() + 1) as usize] =
+                placed.get_tile().get_glyph().to_string();
+        }
         Self::put_boarder(&mut result, len);
 
         result
@@ -84,9 +228,120 @@ impl Board {
     pub fn get_name(&self) -> &str {
         &self.name
     }
+
+    /// Where the `index`-th of `players` snakes starts.
+    ///
+    /// Boards may list their own spawn points; otherwise a single snake starts
+    /// in the middle and several are spread over evenly spaced rows, heading
+    /// in alternating directions. A spawn that would fall on a wall or on an
+    /// earlier spawn moves on to the next free cell, row by row.
+    pub fn get_spawn(&self, index: usize, players: usize) -> Spawn {
+        if let Some(spawn) = self.spawns.get(index) {
+            return spawn.clone();
+        }
+
+        let spawn = self.default_spawn(index, players);
+        let used: Vec<Point> = (0..index)
+            .map(|earlier| self.get_spawn(earlier, players).point)
+            .collect();
+        let size = self.table_size as usize;
+        let start = spawn.point.get_x() as usize * size + spawn.point.get_y() as usize;
+
+        (0..size * size)
+            .map(|step| (start + step) % (size * size))
+            .map(|cell| Point::new((cell / size) as i16, (cell % size) as i16))
+            .find(|point| self.is_open(point) && !used.contains(point))
+            .map_or(spawn.clone(), |point| Spawn::new(point, spawn.direction))
+    }
+
+    pub fn add_spawn(&mut self, spawn: Spawn) {
+        self.spawns.push(spawn);
+    }
+
+    /// Whether every spawn the board lists is on an open cell of its own.
+    pub fn check_spawns(&self) -> Result<(), String> {
+        let valid_range = 0..self.table_size as i16;
+
+        for (index, spawn) in self.spawns.iter().enumerate() {
+            let point = spawn.get_point();
+            if !valid_range.contains(&point.get_x())
+                || !valid_range.contains(&point.get_y())
+                || !self.is_open(point)
+            {
+                return Err(format!(
+                    "Spawn {} of board '{}' is not on an open cell",
+                    index + 1,
+                    self.name
+                ));
+            }
+            if self.spawns[..index]
+                .iter()
+                .any(|earlier| earlier.get_point() == point)
+            {
+                return Err(format!(
+                    "Spawn {} of board '{}' is on an earlier one",
+                    index + 1,
+                    self.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub fn add_entity(&mut self, entity: Entity) {
+        self.entities.push(entity);
+    }
+
+    /// Takes away every entity that starts at `point` or patrols through it.
+    pub fn remove_entities(&mut self, point: &Point) {
+        self.entities.retain(|entity| match entity.get_motion() {
+            Motion::Patrol(path) => !path.contains(point),
+            _ => entity.get_point() != point,
+        });
+    }
+
+    pub fn get_food_rates(&self) -> &FoodRates {
+        &self.food
+    }
+
+    #[cfg(test)]
+    pub fn set_food_rates(&mut self, rates: FoodRates) {
+        self.food = rates;
+    }
+
+    pub fn get_power_up_rate(&self) -> f64 {
+        self.power_ups
+    }
+
+    #[cfg(test)]
+    pub fn set_power_up_rate(&mut self, rate: f64) {
+        self.power_ups = rate;
+    }
 }
 
 impl Board {
+    fn default_spawn(&self, index: usize, players: usize) -> Spawn {
+        let size = self.table_size as i16;
+        let half = (size - 1) / 2;
+
+        if players <= 1 {
+            return Spawn::new(Point::new(half, half), Direction::Right);
+        }
+
+        let row = (index as i16 + 1) * size / (players as i16 + 1);
+        let direction = match index % 2 {
+            0 => Direction::Right,
+            _ => Direction::Left,
+        };
+
+        Spawn::new(Point::new(row, half), direction)
+    }
+
     fn put_walls(result: &mut [Vec<String>], walls: &Walls) {
         walls.iter().for_each(|p| {
             result[(p.get_x() + 1) as usize][(p.get_y() + 1) as usize] = "██".to_string()
@@ -130,7 +385,8 @@ impl<'a> IntoIterator for &'a mut Board {
 
 #[cfg(test)]
 mod test_board {
-    use super::{Board, Wall};
+    use super::{Board, Spawn, Wall};
+    use crate::core::{Direction, Entity, Tile};
 
     #[test]
     fn is_wall() {
@@ -156,4 +412,145 @@ mod test_board {
 
         assert_eq!(board.walls, Vec::from([Wall::new(3, 3), Wall::new(3, 0)]));
     }
+
+    #[test]
+    fn get_spawn() {
+        let mut board = Board::new("test".to_string(), 20, Vec::new());
+
+        assert_eq!(
+            board.get_spawn(0, 1),
+            Spawn::new(Wall::new(9, 9), Direction::Right)
+        );
+        assert_eq!(
+            board.get_spawn(0, 2),
+            Spawn::new(Wall::new(6, 9), Direction::Right)
+        );
+        assert_eq!(
+            board.get_spawn(1, 2),
+            Spawn::new(Wall::new(13, 9), Direction::Left)
+        );
+
+        board.add_spawn(Spawn::new(Wall::new(2, 2), Direction::Down));
+        assert_eq!(
+            board.get_spawn(0, 2),
+            Spawn::new(Wall::new(2, 2), Direction::Down)
+        );
+    }
+
+    #[test]
+    fn spawns_keep_off_walls_and_each_other() {
+        let mut board = Board::new("test".to_string(), 20, Vec::from([Wall::new(9, 9)]));
+        assert_eq!(
+            board.get_spawn(0, 1),
+            Spawn::new(Wall::new(9, 10), Direction::Right)
+        );
+
+        board.add_spawn(Spawn::new(Wall::new(13, 9), Direction::Down));
+        assert_eq!(
+            board.get_spawn(1, 2),
+            Spawn::new(Wall::new(13, 10), Direction::Left)
+        );
+        assert!(board.check_spawns().is_ok());
+
+        board.add_spawn(Spawn::new(Wall::new(13, 9), Direction::Up));
+        assert!(board.check_spawns().is_err());
+
+        let mut board = Board::new("test".to_string(), 20, Vec::from([Wall::new(9, 9)]));
+        board.add_spawn(Spawn::new(Wall::new(9, 9), Direction::Up));
+        assert!(board.check_spawns().is_err());
+
+        let boards: Vec<Board> = serde_json::from_str(include_str!("../boards.json")).unwrap();
+        for board in &boards {
+            assert!(board.check_spawns().is_ok());
+            for players in 1..=4 {
+                let spawns: Vec<Spawn> = (0..players)
+                    .map(|index| board.get_spawn(index, players))
+                    .collect();
+                for (index, spawn) in spawns.iter().enumerate() {
+                    assert!(board.is_open(spawn.get_point()), "{}", board.get_name());
+                    let taken = spawns[..index]
+                        .iter()
+                        .any(|earlier| earlier.get_point() == spawn.get_point());
+                    assert!(!taken, "{}", board.get_name());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn read_without_spawns() {
+        let board: Board =
+            serde_json::from_str(r#"{"name":"old","table_size":5,"walls":[{"x":1,"y":2}]}"#)
+                .unwrap();
+
+        assert!(board.spawns.is_empty());
+        assert!(board.get_food_rates().is_none());
+        assert!(board.is_wall(&Wall::new(1, 2)));
+        assert_eq!(board.get_tile(&Wall::new(1, 2)), Tile::Wall);
+        assert_eq!(board.get_tile(&Wall::new(2, 2)), Tile::Floor);
+    }
+
+    #[test]
+    fn set_tile() {
+        let mut board = Board::new("test".to_string(), 5, Vec::from([Wall::new(1, 1)]));
+
+        board.set_tile(Wall::new(1, 1), Tile::Mud).unwrap();
+        board
+            .set_tile(Wall::new(2, 3), Tile::OneWay(Direction::Left))
+            .unwrap();
+        board.set_tile(Wall::new(4, 4), Tile::Breakable).unwrap();
+        assert!(board.set_tile(Wall::new(5, 0), Tile::Ice).is_err());
+
+        assert!(!board.is_wall(&Wall::new(1, 1)));
+        assert!(board.is_wall(&Wall::new(4, 4)));
+        assert_eq!(board.count_open_cells(), 24);
+        assert_eq!(board.get_table()[3][4], " ←");
+
+        let json = serde_json::to_string(&board).unwrap();
+        let mut board: Board = serde_json::from_str(&json).unwrap();
+        assert_eq!(board.get_tile(&Wall::new(1, 1)), Tile::Mud);
+        assert_eq!(
+            board.get_tile(&Wall::new(2, 3)),
+            Tile::OneWay(Direction::Left)
+        );
+
+        board.set_tile(Wall::new(1, 1), Tile::Floor).unwrap();
+        assert_eq!(board.get_tiles().len(), 2);
+    }
+
+    #[test]
+    fn entities() {
+        let mut board = Board::new("test".to_string(), 6, Vec::new());
+        let path = Vec::from([Wall::new(1, 1), Wall::new(1, 2)]);
+        board.add_entity(Entity::patrol(path).unwrap());
+        board.add_entity(Entity::bounce(Wall::new(3, 3), Direction::Down));
+        board.add_entity(Entity::chase(Wall::new(4, 4)));
+        assert!(Entity::patrol(Vec::new()).is_none());
+
+        let json = serde_json::to_string(&board).unwrap();
+        let mut read: Board = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.get_entities(), board.get_entities());
+
+        read.remove_entities(&Wall::new(1, 2));
+        read.remove_entities(&Wall::new(4, 4));
+        assert_eq!(read.get_entities(), &board.get_entities()[1..2]);
+    }
+
+    #[test]
+    fn portals_come_in_pairs() {
+        let mut board = Board::new("test".to_string(), 6, Vec::new());
+
+        assert_eq!(board.next_portal_pair(), Some(0));
+        board.set_tile(Wall::new(1, 1), Tile::Portal(0)).unwrap();
+        assert_eq!(board.get_partner(&Wall::new(1, 1)), None);
+        assert_eq!(board.next_portal_pair(), Some(0));
+        board.set_tile(Wall::new(4, 2), Tile::Portal(0)).unwrap();
+        assert_eq!(board.next_portal_pair(), Some(1));
+
+        assert_eq!(board.get_partner(&Wall::new(1, 1)), Some(&Wall::new(4, 2)));
+        assert_eq!(board.pass(Wall::new(4, 2), Direction::Up), Wall::new(0, 1));
+        assert_eq!(board.pass(Wall::new(3, 3), Direction::Up), Wall::new(3, 3));
+        assert_eq!(board.count_open_cells(), 34);
+        assert_eq!(board.get_table()[2][2], "@1");
+    }
 }
@@ -26,6 +26,7 @@ impl Boards {
         if self.boards.iter().any(|board| board.get_name() == name) {
             return Err(format!("Board '{}' already exists", name));
         }
+        board.check_spawns()?;
 
         self.boards.push(board);
         Ok(())
@@ -35,6 +36,10 @@ impl Boards {
         self.boards.get(index)
     }
 
+    pub fn get_all(&self) -> &[Board] {
+        &self.boards
+    }
+
     pub fn get_names(&self) -> Vec<String> {
         self.boards
             .iter()
@@ -0,0 +1,253 @@
+mod greedy;
+mod grid;
+mod hamiltonian;
+mod hunter;
+mod neural;
+mod tail_chaser;
+
+pub use greedy::Greedy;
+pub use grid::Grid;
+pub use hamiltonian::{Cycle, Hamiltonian};
+pub use hunter::Hunter;
+pub use neural::{Genome, Neural, GENOME_PATH};
+pub use tail_chaser::TailChaser;
+
+use super::{Direction, Game};
+
+use std::io;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+/// Steers one snake of a game, the way a player does with the keyboard.
+pub trait Controller {
+    /// Where the `player`-th snake of `game` should turn before the next
+    /// walk, or `None` to leave it to someone else.
+    fn decide(&mut self, game: &Game, player: usize) -> Option<Direction>;
+
+    /// Why the controller gave up, after which its snake leaves the game.
+    fn get_forfeit(&self) -> Option<&str> {
+        None
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+    /// Plays with the genome `snake_game train` evolved last.
+    Trained,
+}
+
+impl Difficulty {
+    /// The difficulty after this one, passing by `Trained` only when there
+    /// is a `trained` genome to play with.
+    pub fn next(self, trained: bool) -> Self {
+        match self {
+            Self::Easy => Self::Medium,
+            Self::Medium => Self::Hard,
+            Self::Hard if trained => Self::Trained,
+            Self::Hard | Self::Trained => Self::Easy,
+        }
+    }
+
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Self::Easy => "easy",
+            Self::Medium => "medium",
+            Self::Hard => "hard",
+            Self::Trained => "trained",
+        }
+    }
+
+    /// How many cells ahead the bot plans its way to the food.
+    ///
+    /// An easy bot only looks at the cells next to its head.
+    fn lookahead(self) -> usize {
+        match self {
+            Self::Easy => 1,
+            Self::Medium => 12,
+            Self::Hard | Self::Trained => usize::MAX,
+        }
+    }
+
+    /// How often the bot reacts too late and keeps going straight.
+    fn mistakes(self) -> f64 {
+        match self {
+            Self::Easy => 0.2,
+            Self::Medium => 0.05,
+            Self::Hard | Self::Trained => 0.0,
+        }
+    }
+
+    /// A bot of this difficulty whose mistakes are determined by `seed`.
+    /// Fails for `Trained` when its genome cannot be loaded.
+    pub fn bot(self, seed: u64) -> io::Result<Bot> {
+        let strategies: Vec<Box<dyn Controller>> = match self {
+            Self::Easy => vec![Box::new(Greedy)],
+            Self::Medium => vec![Box::new(Hunter::new(self.lookahead())), Box::new(Greedy)],
+            Self::Hard => vec![
+                Box::new(Hunter::new(self.lookahead())),
+                Box::new(TailChaser),
+                Box::new(Greedy),
+            ],
+            Self::Trained => {
+                return Genome::load(GENOME_PATH)
+                    .map(|genome| Bot::trained(genome, seed))
+                    .map_err(|error| {
+                        let message = format!("Cannot load the trained genome: {error}");
+                        io::Error::new(error.kind(), message)
+                    });
+            }
+        };
+
+        Ok(Bot {
+            strategies,
+            mistakes: self.mistakes(),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        })
+    }
+}
+
+/// A computer player: asks its strategies in order until one knows a way.
+pub struct Bot {
+    strategies: Vec<Box<dyn Controller>>,
+    mistakes: f64,
+    rng: ChaCha8Rng,
+}
+
+impl Controller for Bot {
+    fn decide(&mut self, game: &Game, player: usize) -> Option<Direction> {
+        if !game.get_players()[player].is_alive() || self.rng.gen_bool(self.mistakes) {
+            return None;
+        }
+
+        let choice = self
+            .strategies
+            .iter_mut()
+            .find_map(|strategy| strategy.decide(game, player));
+        match game.get_players()[player].is_boosted() {
+            true => Self::boosted(game, player, choice),
+            false => choice,
+        }
+    }
+}
+
+impl Bot {
+    /// A bot playing with `genome`, whose mistakes are determined by `seed`.
+    pub fn trained(genome: Genome, seed: u64) -> Self {
+        Self {
+            strategies: vec![Box::new(Neural::new(genome))],
+            mistakes: Difficulty::Trained.mistakes(),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Starts the mistakes of the bot over from `seed`, as for a new game.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+
+    /// A boosted snake goes two cells the same way before the bot gets to
+    /// decide again, so a way blocked on its second cell is swapped for one
+    /// that is clear for both, when there is one.
+    fn boosted(game: &Game, player: usize, choice: Option<Direction>) -> Option<Direction> {
+        let grid = Grid::new(game);
+        let size = game.get_board().get_size();
+        let snake = &game.get_players()[player];
+        let head = snake.get_snake_head();
+        let clear = |direction: &Direction| {
+            let first = head.get_neighbor(direction, size);
+            let second = first.get_neighbor(direction, size);
+            grid.can_enter(&first, *direction) && grid.can_enter(&second, *direction)
+        };
+
+        if clear(&choice.unwrap_or(snake.get_direction())) {
+            return choice;
+        }
+        let detour = grid
+            .moves(head)
+            .map(|(direction, _)| direction)
+            .find(|direction| clear(direction));
+        detour.or(choice)
+    }
+}
+
+#[cfg(test)]
+mod test_bot {
+    use super::{Controller, Difficulty};
+    use crate::core::power_up::{self, PowerUpKind};
+    use crate::core::{Board, Game, Snapshot};
+
+    fn play(board: &Board, difficulty: Difficulty, ticks: u32) -> Game<'_> {
+        let mut game = Game::with_seed(board, 3, 7);
+        let mut bot = difficulty.bot(7).unwrap();
+
+        for _ in 0..ticks {
+            if let Some(direction) = bot.decide(&game, 0) {
+                game.rotation(direction);
+            }
+            if !game.walk() {
+                break;
+            }
+        }
+
+        game
+    }
+
+    #[test]
+    fn hard_bot_survives_and_eats() {
+        let board = Board::new("test".to_string(), 10, Vec::new());
+        let game = play(&board, Difficulty::Hard, 500);
+
+        assert!(game.get_death().is_none());
+        assert!(game.get_score() >= 10);
+    }
+
+    #[test]
+    fn every_difficulty_eats() {
+        let board = Board::new("test".to_string(), 10, Vec::new());
+
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            assert!(play(&board, difficulty, 100).get_score() > 0);
+        }
+    }
+
+    #[test]
+    fn trained_bots_only_come_with_a_genome() {
+        assert_eq!(Difficulty::Hard.next(false), Difficulty::Easy);
+        assert_eq!(Difficulty::Hard.next(true), Difficulty::Trained);
+        assert_eq!(Difficulty::Trained.next(true), Difficulty::Easy);
+    }
+
+    #[test]
+    fn boosted_bot_turns_before_a_wall_two_cells_ahead() {
+        let open = Board::new("test".to_string(), 10, Vec::new());
+        let start = Game::with_seed(&open, 3, 7).snapshot();
+        let mut players = start.get_players().to_vec();
+        let direction = players[0].get_direction();
+        let food = players[0].get_snake_head().get_neighbor(&direction, 10);
+        let wall = food.get_neighbor(&direction, 10);
+        power_up::stack(&mut players[0].effects, PowerUpKind::SpeedBoost);
+
+        let board = Board::new("test".to_string(), 10, Vec::from([wall]));
+        let snapshot = Snapshot::new(
+            players,
+            food,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            0,
+        );
+        let mut game = Game::from_snapshot(&board, snapshot);
+        let choice = Difficulty::Hard.bot(7).unwrap().decide(&game, 0);
+
+        assert!(choice.is_some_and(|choice| choice != direction));
+        game.rotation(choice.unwrap());
+        assert!(game.walk());
+    }
+}
@@ -0,0 +1,20 @@
+use super::{
+    super::{Direction, Game},
+    Controller, Grid,
+};
+
+/// Heads for the food the shortest way around, looking no further than the
+/// cells next to its head.
+pub struct Greedy;
+
+impl Controller for Greedy {
+    fn decide(&mut self, game: &Game, player: usize) -> Option<Direction> {
+        let grid = Grid::new(game);
+        let head = game.get_players()[player].get_snake_head();
+        let food = game.get_food();
+
+        grid.moves(head)
+            .min_by_key(|(_, point)| grid.distance(point, food))
+            .map(|(direction, _)| direction)
+    }
+}
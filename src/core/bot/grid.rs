@@ -0,0 +1,239 @@
+use std::collections::VecDeque;
+
+use super::super::{point::Point, Direction, FoodKind, Game, Tile};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// The board as a bot sees it: which cells a head may move into.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    size: u16,
+    blocked: Vec<bool>,
+    /// The tile of each cell, for the ways one-way tiles turn heads away.
+    tiles: Vec<Tile>,
+}
+
+impl Grid {
+    /// Blocks the walls, obstacles, portals and entities of `game`, its poison
+    /// and every snake, including the ones that are out.
+    pub fn new(game: &Game) -> Self {
+        let board = game.get_board();
+        let size = board.get_size();
+
+        let mut grid = Self {
+            size,
+            blocked: vec![false; size as usize * size as usize],
+            tiles: vec![Tile::Floor; size as usize * size as usize],
+        };
+
+        for x in 0..size as i16 {
+            for y in 0..size as i16 {
+                let point = Point::new(x, y);
+                let tile = board.get_tile(&point);
+                if game.is_wall(&point) || matches!(tile, Tile::Portal(_)) {
+                    grid.block(&point);
+                }
+                let index = grid.index(&point);
+                grid.tiles[index] = tile;
+            }
+        }
+
+        for player in game.get_players() {
+            player
+                .get_snake()
+                .iter()
+                .for_each(|point| grid.block(point));
+        }
+
+        game.get_movers()
+            .iter()
+            .for_each(|mover| grid.block(mover.get_point()));
+        game.get_items()
+            .iter()
+            .filter(|item| item.get_kind() == FoodKind::Poison)
+            .for_each(|item| grid.block(item.get_point()));
+
+        grid
+    }
+
+    pub fn is_free(&self, point: &Point) -> bool {
+        !self.blocked[self.index(point)]
+    }
+
+    pub fn block(&mut self, point: &Point) {
+        let index = self.index(point);
+        self.blocked[index] = true;
+    }
+
+    pub fn free(&mut self, point: &Point) {
+        let index = self.index(point);
+        self.blocked[index] = false;
+    }
+
+    /// The cells next to `from`, wrapping around the edges like the snake does.
+    pub fn neighbors(&self, from: &Point) -> impl Iterator<Item = (Direction, Point)> + '_ {
+        let from = from.clone();
+
+        DIRECTIONS
+            .into_iter()
+            .map(move |direction| (direction, from.get_neighbor(&direction, self.size)))
+    }
+
+    /// Whether a head heading `direction` may move into `point`: it is free
+    /// and, if it is a one-way tile, entered its way.
+    pub fn can_enter(&self, point: &Point, direction: Direction) -> bool {
+        self.is_free(point) && self.lets_in(point, direction)
+    }
+
+    /// The free cells next to `from` that let a head in from it.
+    pub fn moves(&self, from: &Point) -> impl Iterator<Item = (Direction, Point)> + '_ {
+        self.neighbors(from)
+            .filter(|(direction, point)| self.can_enter(point, *direction))
+    }
+
+    /// The steps of a shortest way from `from` over free cells to a cell
+    /// accepted by `goal`, which may be blocked itself, entering every cell
+    /// the way its tile lets in. Gives up on ways longer than `limit`.
+    pub fn path(
+        &self,
+        from: &Point,
+        goal: impl Fn(&Point) -> bool,
+        limit: usize,
+    ) -> Option<Vec<(Direction, Point)>> {
+        let mut previous: Vec<Option<(Direction, Point)>> = vec![None; self.blocked.len()];
+        let mut visited = vec![false; self.blocked.len()];
+        let mut queue = VecDeque::from([(from.clone(), 0)]);
+        visited[self.index(from)] = true;
+
+        while let Some((point, distance)) = queue.pop_front() {
+            if distance >= limit {
+                continue;
+            }
+
+            for (direction, next) in self.neighbors(&point) {
+                let index = self.index(&next);
+                if visited[index] || !self.lets_in(&next, direction) {
+                    continue;
+                }
+
+                if goal(&next) {
+                    previous[index] = Some((direction, point));
+                    return Some(self.unwind(&previous, next));
+                }
+
+                if self.is_free(&next) {
+                    visited[index] = true;
+                    previous[index] = Some((direction, point.clone()));
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// How many other free cells can be reached from `from`.
+    pub fn reachable(&self, from: &Point) -> usize {
+        let mut visited = vec![false; self.blocked.len()];
+        let mut stack = vec![from.clone()];
+        visited[self.index(from)] = true;
+        let mut count = 0;
+
+        while let Some(point) = stack.pop() {
+            for (_, next) in self.moves(&point) {
+                let index = self.index(&next);
+                if !visited[index] {
+                    visited[index] = true;
+                    count += 1;
+                    stack.push(next);
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Length of the shortest way from `from` to `to` ignoring anything in between.
+    pub fn distance(&self, from: &Point, to: &Point) -> u16 {
+        from.distance(to, self.size) as u16
+    }
+}
+
+impl Grid {
+    fn index(&self, point: &Point) -> usize {
+        point.get_x() as usize * self.size as usize + point.get_y() as usize
+    }
+
+    fn lets_in(&self, point: &Point, direction: Direction) -> bool {
+        self.tiles[self.index(point)].lets_in(direction)
+    }
+
+    fn unwind(
+        &self,
+        previous: &[Option<(Direction, Point)>],
+        goal: Point,
+    ) -> Vec<(Direction, Point)> {
+        let mut steps = Vec::new();
+        let mut point = goal;
+
+        while let Some((direction, from)) = previous[self.index(&point)].clone() {
+            steps.push((direction, point));
+            point = from;
+        }
+
+        steps.reverse();
+        steps
+    }
+}
+
+#[cfg(test)]
+mod test_grid {
+    use super::Grid;
+    use crate::core::{Board, Direction, Game, Point, Tile, Wall};
+
+    #[test]
+    fn path_wraps_around() {
+        let board = Board::new("test".to_string(), 7, Vec::from([Wall::new(3, 0)]));
+        let game = Game::with_seed(&board, 3, 1);
+        let grid = Grid::new(&game);
+
+        assert!(!grid.is_free(&Point::new(3, 0)));
+        assert!(!grid.is_free(&Point::new(3, 3)));
+
+        let head = Point::new(0, 0);
+        let path = grid.path(&head, |p| *p == Point::new(6, 0), 10).unwrap();
+        assert_eq!(path, vec![(Direction::Up, Point::new(6, 0))]);
+
+        let path = grid.path(&head, |p| *p == Point::new(4, 0), 10).unwrap();
+        assert_eq!(path.len(), 3);
+        assert!(grid.path(&head, |p| *p == Point::new(4, 0), 2).is_none());
+
+        assert_eq!(grid.distance(&head, &Point::new(6, 6)), 2);
+        assert_eq!(grid.reachable(&head), 7 * 7 - 4 - 1);
+    }
+
+    #[test]
+    fn one_way_tiles_turn_paths_away() {
+        let mut board = Board::new("test".to_string(), 7, Vec::new());
+        board
+            .set_tile(Point::new(1, 0), Tile::OneWay(Direction::Down))
+            .unwrap();
+        let game = Game::with_seed(&board, 3, 1);
+        let grid = Grid::new(&game);
+
+        let head = Point::new(2, 0);
+        assert!(grid
+            .moves(&head)
+            .all(|(direction, _)| direction != Direction::Up));
+        let path = grid.path(&head, |p| *p == Point::new(0, 0), 10).unwrap();
+        assert_eq!(path.len(), 4);
+
+        let path = grid.path(&Point::new(0, 0), |p| *p == head, 10).unwrap();
+        assert_eq!(path.len(), 2);
+    }
+}
@@ -0,0 +1,447 @@
+use super::{
+    super::{point::Point, Board, Direction, Game, Tile},
+    Controller,
+};
+
+/// How many cells the search may try before it gives up on a board.
+const SEARCH_BUDGET: usize = 2_000_000;
+/// Free cells kept between the head and the rest of the body when taking a
+/// shortcut, so growing after a meal cannot close the way.
+const SHORTCUT_MARGIN: usize = 4;
+
+/// Why a board has no `Cycle`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NoCycle {
+    /// Some free cells cannot be reached from the others.
+    Disconnected,
+    /// A free cell has fewer than two free neighbors.
+    DeadEnd(Point),
+    /// The board colors like a chessboard with more cells of one color.
+    Parity,
+    /// Every way through the board was tried.
+    Exhausted,
+    /// The search ran out of budget before finding a cycle or ruling it out.
+    GaveUp,
+    /// Power-ups turn up on the board, and a speed boost would carry the
+    /// snake past the turns of the tour.
+    PowerUps,
+    /// One-way tiles, ice or speed pads would keep the snake from following
+    /// the tour.
+    Tiles,
+}
+
+impl NoCycle {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Disconnected => "Some free cells cannot be reached from the others.".to_string(),
+            Self::DeadEnd(point) => format!(
+                "The cell ({}, {}) has fewer than two free neighbors.",
+                point.get_x(),
+                point.get_y()
+            ),
+            Self::Parity => "The free cells have unequal chessboard colors.".to_string(),
+            Self::Exhausted => "No way through every free cell returns to its start.".to_string(),
+            Self::GaveUp => "The search gave up before finding a cycle.".to_string(),
+            Self::PowerUps => "A speed boost would carry the snake off the tour.".to_string(),
+            Self::Tiles => {
+                "One-way tiles, ice or speed pads would turn the snake off the tour.".to_string()
+            }
+        }
+    }
+}
+
+/// A closed tour visiting every free cell of a board exactly once.
+///
+/// A snake that follows it can never trap itself, so it fills the board.
+#[derive(Debug, Clone)]
+pub struct Cycle {
+    size: u16,
+    tour: Vec<Point>,
+    /// Where each cell of the board comes in `tour`, `None` for walls.
+    position: Vec<Option<usize>>,
+}
+
+impl Cycle {
+    pub fn find(board: &Board) -> Result<Self, NoCycle> {
+        if board.get_power_up_rate() > 0.0 {
+            return Err(NoCycle::PowerUps);
+        }
+        let bends = |tile: Tile| matches!(tile, Tile::OneWay(_) | Tile::Ice | Tile::SpeedPad);
+        if board
+            .get_tiles()
+            .iter()
+            .any(|placed| bends(placed.get_tile()))
+        {
+            return Err(NoCycle::Tiles);
+        }
+
+        let size = board.get_size();
+        let cells = (0..size as i16)
+            .flat_map(|x| (0..size as i16).map(move |y| Point::new(x, y)))
+            .filter(|point| board.is_open(point))
+            .collect::<Vec<_>>();
+
+        let mut index = vec![None; size as usize * size as usize];
+        for (i, point) in cells.iter().enumerate() {
+            index[Self::cell(size, point)] = Some(i);
+        }
+
+        let mut neighbors: Vec<Vec<usize>> = cells
+            .iter()
+            .map(|point| {
+                [
+                    Direction::Up,
+                    Direction::Down,
+                    Direction::Left,
+                    Direction::Right,
+                ]
+                .iter()
+                .filter_map(|direction| {
+                    index[Self::cell(size, &point.get_neighbor(direction, size))]
+                })
+                .collect()
+            })
+            .collect();
+        // a board of two cells in a row reaches the same neighbor both ways
+        neighbors.iter_mut().for_each(|n| {
+            n.sort();
+            n.dedup()
+        });
+
+        Self::check(size, &cells, &neighbors)?;
+
+        let order = Search::new(&neighbors).run()?;
+        let tour: Vec<Point> = order.iter().map(|i| cells[*i].clone()).collect();
+
+        let mut position = vec![None; size as usize * size as usize];
+        for (i, point) in tour.iter().enumerate() {
+            position[Self::cell(size, point)] = Some(i);
+        }
+
+        Ok(Self {
+            size,
+            tour,
+            position,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.tour.len()
+    }
+
+    /// Where `point` comes in the tour, `None` for walls.
+    pub fn position(&self, point: &Point) -> Option<usize> {
+        self.position[Self::cell(self.size, point)]
+    }
+
+    /// How many steps along the tour it takes from `from` to `to`.
+    pub fn distance(&self, from: &Point, to: &Point) -> Option<usize> {
+        let (from, to) = (self.position(from)?, self.position(to)?);
+
+        Some((to + self.len() - from) % self.len())
+    }
+
+    fn cell(size: u16, point: &Point) -> usize {
+        point.get_x() as usize * size as usize + point.get_y() as usize
+    }
+
+    /// Rules out boards that cannot have a cycle without searching them.
+    fn check(size: u16, cells: &[Point], neighbors: &[Vec<usize>]) -> Result<(), NoCycle> {
+        if cells.len() < 2 {
+            return Err(NoCycle::Disconnected);
+        }
+
+        if let Some(i) = (0..cells.len()).find(|i| neighbors[*i].len() < 2) {
+            return Err(NoCycle::DeadEnd(cells[i].clone()));
+        }
+
+        let mut seen = vec![false; cells.len()];
+        let mut stack = vec![0];
+        seen[0] = true;
+        while let Some(i) = stack.pop() {
+            for &next in &neighbors[i] {
+                if !seen[next] {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        if seen.contains(&false) {
+            return Err(NoCycle::Disconnected);
+        }
+
+        // an odd board wraps a cell around to one of its own color
+        let black = cells
+            .iter()
+            .filter(|p| (p.get_x() + p.get_y()) % 2 == 0)
+            .count();
+        if size.is_multiple_of(2) && black * 2 != cells.len() {
+            return Err(NoCycle::Parity);
+        }
+
+        Ok(())
+    }
+}
+
+/// A backtracking search for a cycle through every cell of a graph.
+///
+/// It goes to the cell with the fewest ways on first and prunes as soon as
+/// some cell could no longer be entered and left again.
+struct Search<'a> {
+    neighbors: &'a [Vec<usize>],
+    visited: Vec<bool>,
+    path: Vec<usize>,
+    budget: usize,
+}
+
+impl<'a> Search<'a> {
+    fn new(neighbors: &'a [Vec<usize>]) -> Self {
+        Self {
+            neighbors,
+            visited: vec![false; neighbors.len()],
+            path: Vec::with_capacity(neighbors.len()),
+            budget: SEARCH_BUDGET,
+        }
+    }
+
+    fn run(mut self) -> Result<Vec<usize>, NoCycle> {
+        let start = (0..self.neighbors.len())
+            .min_by_key(|i| self.neighbors[*i].len())
+            .unwrap_or(0);
+
+        self.visited[start] = true;
+        self.path.push(start);
+
+        match self.extend() {
+            Some(true) => Ok(self.path),
+            Some(false) => Err(NoCycle::Exhausted),
+            None => Err(NoCycle::GaveUp),
+        }
+    }
+
+    /// Tries every way to go on from the end of the path. `None` once the
+    /// budget is spent.
+    fn extend(&mut self) -> Option<bool> {
+        let head = *self.path.last()?;
+        let start = self.path[0];
+
+        if self.path.len() == self.neighbors.len() {
+            return Some(self.neighbors[head].contains(&start));
+        }
+
+        if self.budget == 0 {
+            return None;
+        }
+        self.budget -= 1;
+
+        if !self.is_connected(head) {
+            return Some(false);
+        }
+
+        let mut candidates: Vec<usize> = self.neighbors[head]
+            .iter()
+            .copied()
+            .filter(|next| !self.visited[*next])
+            .collect();
+
+        // a neighbor whose only other way is the head has to come next, unless
+        // the head is the start and the neighbor can wait to close the cycle
+        let forced: Vec<usize> = match self.path.len() {
+            1 => Vec::new(),
+            _ => candidates
+                .iter()
+                .copied()
+                .filter(|next| self.ways(*next, head) == 2)
+                .collect(),
+        };
+        match forced.len() {
+            0 => candidates.sort_by_key(|next| self.ways(*next, head)),
+            1 => candidates = forced,
+            _ => return Some(false),
+        }
+
+        for next in candidates {
+            self.visited[next] = true;
+            self.path.push(next);
+
+            let stuck = self.neighbors[head]
+                .iter()
+                .any(|cell| !self.visited[*cell] && self.ways(*cell, next) < 2);
+
+            if !stuck {
+                match self.extend() {
+                    Some(false) => {}
+                    found => return found,
+                }
+            }
+
+            self.path.pop();
+            self.visited[next] = false;
+        }
+
+        Some(false)
+    }
+
+    /// How many neighbors of `cell` a cycle could still use to enter or leave it.
+    fn ways(&self, cell: usize, head: usize) -> usize {
+        let start = self.path[0];
+
+        self.neighbors[cell]
+            .iter()
+            .filter(|n| !self.visited[**n] || **n == head || **n == start)
+            .count()
+    }
+
+    /// Whether every unvisited cell can still be reached from `head`.
+    fn is_connected(&self, head: usize) -> bool {
+        let mut seen = self.visited.clone();
+        let mut stack = vec![head];
+        let mut reached = 0;
+
+        while let Some(cell) = stack.pop() {
+            for &next in &self.neighbors[cell] {
+                if !seen[next] {
+                    seen[next] = true;
+                    reached += 1;
+                    stack.push(next);
+                }
+            }
+        }
+
+        reached == self.neighbors.len() - self.path.len()
+    }
+}
+
+/// Follows a `Cycle` around the board, cutting across it while the snake is
+/// short enough that doing so cannot trap it.
+pub struct Hamiltonian {
+    cycle: Cycle,
+}
+
+impl Hamiltonian {
+    pub fn new(cycle: Cycle) -> Self {
+        Self { cycle }
+    }
+}
+
+impl Controller for Hamiltonian {
+    fn decide(&mut self, game: &Game, player: usize) -> Option<Direction> {
+        let snake = game.get_players()[player].get_snake();
+        let head = snake.front()?;
+        let cycle = &self.cycle;
+
+        // the first body cell ahead on the tour; everything before it is free
+        let room = snake
+            .iter()
+            .skip(1)
+            .filter_map(|point| cycle.distance(head, point))
+            .min()
+            .unwrap_or(cycle.len());
+        let food = cycle.distance(head, game.get_food())?;
+        let shortcuts = snake.len() * 2 < cycle.len();
+
+        [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .filter_map(|direction| {
+            let next = head.get_neighbor(&direction, game.get_board().get_size());
+            Some((direction, cycle.distance(head, &next)?))
+        })
+        .filter(|(_, steps)| {
+            *steps == 1 && *steps < room
+                || shortcuts && *steps > 1 && *steps <= food && steps + SHORTCUT_MARGIN < room
+        })
+        .max_by_key(|(_, steps)| *steps)
+        .map(|(direction, _)| direction)
+    }
+}
+
+#[cfg(test)]
+mod test_hamiltonian {
+    use super::{Cycle, Hamiltonian, NoCycle};
+    use crate::core::{bot::Controller, Board, Direction, Game, Point, Tile, Wall};
+
+    fn check_tour(board: &Board, cycle: &Cycle) {
+        let size = board.get_size();
+        assert_eq!(cycle.len(), board.count_open_cells());
+
+        for (index, point) in cycle.tour.iter().enumerate() {
+            let next = &cycle.tour[(index + 1) % cycle.len()];
+            assert_eq!(
+                point.get_neighbor(&point.direction_of_neighbor(next), size),
+                *next
+            );
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        for size in [4, 5, 7] {
+            let board = Board::new("test".to_string(), size, Vec::new());
+            check_tour(&board, &Cycle::find(&board).unwrap());
+        }
+
+        let board = Board::new(
+            "test".to_string(),
+            6,
+            Vec::from([Wall::new(0, 0), Wall::new(0, 1)]),
+        );
+        check_tour(&board, &Cycle::find(&board).unwrap());
+    }
+
+    #[test]
+    fn reports_missing_cycles() {
+        let walls = Vec::from([Wall::new(0, 1), Wall::new(1, 0), Wall::new(5, 0)]);
+        let board = Board::new("test".to_string(), 6, walls);
+        assert_eq!(
+            Cycle::find(&board).unwrap_err(),
+            NoCycle::DeadEnd(Point::new(0, 0))
+        );
+
+        let board = Board::new("test".to_string(), 6, Vec::from([Wall::new(2, 2)]));
+        assert_eq!(Cycle::find(&board).unwrap_err(), NoCycle::Parity);
+
+        let walls = (0..6)
+            .flat_map(|i| [Wall::new(i, 0), Wall::new(i, 3)])
+            .collect();
+        let board = Board::new("test".to_string(), 6, walls);
+        assert_eq!(Cycle::find(&board).unwrap_err(), NoCycle::Disconnected);
+
+        let mut board = Board::new("test".to_string(), 6, Vec::new());
+        board.set_power_up_rate(0.01);
+        assert_eq!(Cycle::find(&board).unwrap_err(), NoCycle::PowerUps);
+
+        for tile in [Tile::OneWay(Direction::Up), Tile::Ice, Tile::SpeedPad] {
+            let mut board = Board::new("test".to_string(), 6, Vec::new());
+            board.set_tile(Point::new(2, 2), tile).unwrap();
+            assert_eq!(Cycle::find(&board).unwrap_err(), NoCycle::Tiles);
+        }
+    }
+
+    #[test]
+    fn clears_the_board() {
+        let board = Board::new(
+            "test".to_string(),
+            6,
+            Vec::from([Wall::new(2, 2), Wall::new(3, 2)]),
+        );
+        let mut bot = Hamiltonian::new(Cycle::find(&board).unwrap());
+        let mut game = Game::with_seed(&board, 3, 11);
+
+        loop {
+            if let Some(direction) = bot.decide(&game, 0) {
+                game.rotation(direction);
+            }
+            if !game.walk() {
+                break;
+            }
+        }
+
+        assert!(game.is_cleared());
+        assert_eq!(game.get_score() as usize, board.count_open_cells() - 3);
+    }
+}
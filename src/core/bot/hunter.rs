@@ -0,0 +1,61 @@
+use std::collections::VecDeque;
+
+use super::{
+    super::{point::Point, Direction, Game, Player},
+    Controller, Grid,
+};
+
+/// Takes the shortest way to the food, but only if the snake can still
+/// reach its own tail once it has eaten.
+pub struct Hunter {
+    lookahead: usize,
+}
+
+impl Hunter {
+    /// A hunter that ignores food more than `lookahead` cells away.
+    pub fn new(lookahead: usize) -> Self {
+        Self { lookahead }
+    }
+
+    /// Follows `path` with a copy of the snake and checks there is a way out
+    /// from where it ends up.
+    fn is_safe(grid: &Grid, player: &Player, path: &[(Direction, Point)]) -> bool {
+        let mut grid = grid.clone();
+        let mut snake: VecDeque<Point> = player.get_snake().iter().cloned().collect();
+
+        for (index, (_, point)) in path.iter().enumerate() {
+            snake.push_front(point.clone());
+            grid.block(point);
+
+            // the last step eats, so the snake keeps its tail there
+            if index + 1 < path.len() {
+                if let Some(tail) = snake.pop_back() {
+                    grid.free(&tail);
+                }
+            }
+        }
+
+        let (Some(head), Some(tail)) = (snake.front(), snake.back()) else {
+            return false;
+        };
+
+        grid.path(head, |point| point == tail, usize::MAX)
+            .is_some_and(|way| way.len() > 1)
+    }
+}
+
+impl Controller for Hunter {
+    fn decide(&mut self, game: &Game, player: usize) -> Option<Direction> {
+        let grid = Grid::new(game);
+        let snake = &game.get_players()[player];
+        let food = game.get_food();
+
+        let path = grid.path(
+            snake.get_snake_head(),
+            |point| point == food,
+            self.lookahead,
+        )?;
+
+        Self::is_safe(&grid, snake, &path).then(|| path[0].0)
+    }
+}
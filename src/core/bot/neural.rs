@@ -0,0 +1,212 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    path::Path,
+};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    super::{Direction, Game},
+    Controller, Grid,
+};
+
+/// Where `snake_game train` keeps the best genome and the trained bots find it.
+pub const GENOME_PATH: &str = "./src/genome.json";
+
+/// What the network is told about each of the three moves it can make.
+const MOVE_FEATURES: usize = 4;
+/// The network's inputs: the features of going straight, left and right,
+/// then how much of the board the snake fills.
+const INPUTS: usize = 3 * MOVE_FEATURES + 1;
+const HIDDEN: usize = 8;
+/// One output per move; the highest one wins.
+const OUTPUTS: usize = 3;
+
+/// The weights of a small neural network that steers a snake.
+///
+/// It has one hidden layer and looks at the cells next to the head: whether
+/// they are free, bring the snake closer to the food or hold it, and how
+/// much room the snake would have left there.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Genome {
+    weights: Vec<f32>,
+}
+
+impl Genome {
+    /// How many weights, biases included, the network has.
+    pub const LEN: usize = (INPUTS + 1) * HIDDEN + (HIDDEN + 1) * OUTPUTS;
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            weights: (0..Self::LEN).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+        }
+    }
+
+    /// A child taking every weight from either parent.
+    pub fn crossover(&self, other: &Self, rng: &mut impl Rng) -> Self {
+        Self {
+            weights: self
+                .weights
+                .iter()
+                .zip(&other.weights)
+                .map(|(a, b)| if rng.gen_bool(0.5) { *a } else { *b })
+                .collect(),
+        }
+    }
+
+    /// Nudges each weight with probability `rate` by up to `scale` either way.
+    pub fn mutate(&mut self, rate: f64, scale: f32, rng: &mut impl Rng) {
+        for weight in self.weights.iter_mut() {
+            if rng.gen_bool(rate) {
+                *weight += rng.gen_range(-scale..=scale);
+            }
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let genome: Self = serde_json::from_reader(reader)?;
+
+        match genome.weights.len() == Self::LEN {
+            true => Ok(genome),
+            false => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "a genome has {} weights, not {}",
+                    genome.weights.len(),
+                    Self::LEN
+                ),
+            )),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    fn think(&self, inputs: &[f32; INPUTS]) -> [f32; OUTPUTS] {
+        let (hidden_weights, output_weights) = self.weights.split_at((INPUTS + 1) * HIDDEN);
+
+        let hidden: Vec<f32> = hidden_weights
+            .chunks(INPUTS + 1)
+            .map(|row| {
+                let sum: f32 = row.iter().zip(inputs).map(|(w, i)| w * i).sum();
+                (sum + row[INPUTS]).tanh()
+            })
+            .collect();
+
+        let mut outputs = [0.0; OUTPUTS];
+        for (output, row) in outputs.iter_mut().zip(output_weights.chunks(HIDDEN + 1)) {
+            *output = row.iter().zip(&hidden).map(|(w, h)| w * h).sum::<f32>() + row[HIDDEN];
+        }
+
+        outputs
+    }
+}
+
+/// Steers with the network of a `Genome`.
+pub struct Neural {
+    genome: Genome,
+}
+
+impl Neural {
+    pub fn new(genome: Genome) -> Self {
+        Self { genome }
+    }
+
+    /// Where going straight, left and right lead, in that order.
+    fn turns(heading: Direction) -> [Direction; 3] {
+        match heading {
+            Direction::Up | Direction::None => [Direction::Up, Direction::Left, Direction::Right],
+            Direction::Down => [Direction::Down, Direction::Right, Direction::Left],
+            Direction::Left => [Direction::Left, Direction::Down, Direction::Up],
+            Direction::Right => [Direction::Right, Direction::Up, Direction::Down],
+        }
+    }
+}
+
+impl Controller for Neural {
+    fn decide(&mut self, game: &Game, player: usize) -> Option<Direction> {
+        let snake = &game.get_players()[player];
+        let head = snake.get_snake().front()?;
+        let food = game.get_food();
+        let size = game.get_board().get_size();
+        let grid = Grid::new(game);
+        let cells = (size as usize * size as usize) as f32;
+
+        let turns = Self::turns(snake.get_direction());
+        let mut inputs = [0.0; INPUTS];
+        for (features, direction) in inputs.chunks_mut(MOVE_FEATURES).zip(turns) {
+            let next = head.get_neighbor(&direction, size);
+            if !grid.is_free(&next) {
+                continue;
+            }
+
+            features[0] = 1.0;
+            features[1] = grid.distance(head, food) as f32 - grid.distance(&next, food) as f32;
+            features[2] = (next == *food) as u8 as f32;
+            features[3] = grid.reachable(&next) as f32 / cells;
+        }
+        inputs[INPUTS - 1] = snake.get_snake().len() as f32 / cells;
+
+        let outputs = self.genome.think(&inputs);
+        let best = (0..OUTPUTS).max_by(|a, b| outputs[*a].total_cmp(&outputs[*b]))?;
+
+        Some(turns[best])
+    }
+}
+
+#[cfg(test)]
+mod test_neural {
+    use super::{Genome, Neural};
+    use crate::core::{bot::Controller, Board, Direction, Game, Wall};
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn genomes_breed_and_save() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mother = Genome::random(&mut rng);
+        let father = Genome::random(&mut rng);
+        assert_eq!(mother.weights.len(), Genome::LEN);
+
+        let mut child = mother.crossover(&father, &mut rng);
+        assert!(child
+            .weights
+            .iter()
+            .enumerate()
+            .all(|(i, w)| *w == mother.weights[i] || *w == father.weights[i]));
+        child.mutate(1.0, 0.1, &mut rng);
+        assert_ne!(child, mother);
+
+        let path = std::env::temp_dir().join("snake_game_genome_test.json");
+        child.save(&path).unwrap();
+        assert_eq!(Genome::load(&path).unwrap(), child);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn avoids_blocked_moves() {
+        // only the first feature, the move being free, counts
+        let mut genome = Genome {
+            weights: vec![0.0; Genome::LEN],
+        };
+        let hidden = (super::INPUTS + 1) * super::HIDDEN;
+        for turn in 0..3 {
+            genome.weights[turn * (super::INPUTS + 1) + turn * super::MOVE_FEATURES] = 1.0;
+            genome.weights[hidden + turn * (super::HIDDEN + 1) + turn] = 1.0;
+        }
+
+        // a wall right ahead of the snake heading right
+        let board = Board::new("test".to_string(), 9, Vec::from([Wall::new(4, 6)]));
+        let game = Game::with_seed(&board, 3, 1);
+
+        let direction = Neural::new(genome).decide(&game, 0);
+        assert!(matches!(direction, Some(Direction::Up | Direction::Down)));
+    }
+}
@@ -0,0 +1,27 @@
+use super::{
+    super::{Direction, Game},
+    Controller, Grid,
+};
+
+/// Stays alive rather than eating: keeps a way to its own tail open and
+/// takes the move with the most room around it.
+pub struct TailChaser;
+
+impl Controller for TailChaser {
+    fn decide(&mut self, game: &Game, player: usize) -> Option<Direction> {
+        let grid = Grid::new(game);
+        let snake = game.get_players()[player].get_snake();
+        let (head, tail) = (snake.front()?, snake.back()?);
+
+        grid.moves(head)
+            .max_by_key(|(_, point)| {
+                let mut after = grid.clone();
+                after.block(point);
+                after.free(tail);
+
+                let reaches_tail = after.path(point, |p| p == tail, usize::MAX).is_some();
+                (reaches_tail, after.reachable(point))
+            })
+            .map(|(direction, _)| direction)
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, Write},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Board, Game};
+
+const JSON_FILE_PATH: &str = "./src/campaign.json";
+const ANONYMOUS: &str = "anonymous";
+
+/// What a campaign level asks for.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    /// Eat this much food.
+    Score(u16),
+    /// Grow the snake this long.
+    Length(u16),
+    /// Stay alive for this many walks.
+    Time(u32),
+}
+
+impl Target {
+    pub fn is_met(&self, game: &Game) -> bool {
+        match *self {
+            Target::Score(score) => game.get_score() >= score,
+            Target::Length(length) => game.get_players()[0].get_snake().len() >= length as usize,
+            Target::Time(ticks) => game.get_ticks() >= ticks,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Target::Score(score) => format!("Eat {} food", score),
+            Target::Length(length) => format!("Grow {} cells long", length),
+            Target::Time(ticks) => format!("Survive {} steps", ticks),
+        }
+    }
+}
+
+/// One step of a campaign: a board and what to do on it.
+#[derive(Debug, Clone)]
+pub struct Level {
+    board: Board,
+    target: Target,
+}
+
+impl Level {
+    pub fn new(board: Board, target: Target) -> Self {
+        Self { board, target }
+    }
+
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn get_target(&self) -> Target {
+        self.target
+    }
+}
+
+/// Levels played in order, each unlocked by completing the one before.
+#[derive(Debug, Clone)]
+pub struct Campaign {
+    levels: Vec<Level>,
+}
+
+impl Campaign {
+    /// Goes through `boards` three times, asking for food first, then for
+    /// staying alive and at last for length.
+    pub fn from_boards(boards: &[Board]) -> Self {
+        let targets = [Target::Score(10), Target::Time(400), Target::Length(25)];
+
+        Self {
+            levels: targets
+                .iter()
+                .flat_map(|target| {
+                    boards
+                        .iter()
+                        .map(|board| Level::new(board.clone(), *target))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get_levels(&self) -> &[Level] {
+        &self.levels
+    }
+
+    /// Whether `level` can be played once `completed` levels are done.
+    pub fn is_unlocked(&self, level: usize, completed: usize) -> bool {
+        level < self.levels.len() && level <= completed
+    }
+}
+
+/// The campaign levels every player completed, each kept by its board name
+/// and target, so that adding or removing boards does not mix them up.
+#[derive(Debug)]
+pub struct Progress {
+    progress: HashMap<String, Vec<(String, Target)>>,
+}
+
+impl Progress {
+    /// Starts without any progress when nobody played the campaign yet.
+    pub fn new() -> Self {
+        let progress = File::open(JSON_FILE_PATH)
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default();
+
+        Self { progress }
+    }
+
+    /// How many levels of `campaign` from the first on `name` completed in
+    /// a row.
+    pub fn get_completed(&self, name: &str, campaign: &Campaign) -> usize {
+        campaign
+            .get_levels()
+            .iter()
+            .take_while(|level| self.is_completed(name, level))
+            .count()
+    }
+
+    pub fn is_completed(&self, name: &str, level: &Level) -> bool {
+        self.progress
+            .get(Self::player(name))
+            .is_some_and(|completed| completed.contains(&Self::key(level)))
+    }
+
+    /// Marks `level` as done for `name`, unlocking the one after it.
+    pub fn complete(&mut self, name: &str, level: &Level) {
+        if self.is_completed(name, level) {
+            return;
+        }
+
+        self.progress
+            .entry(Self::player(name).to_string())
+            .or_default()
+            .push(Self::key(level));
+    }
+
+    fn key(level: &Level) -> (String, Target) {
+        (level.get_board().get_name().to_string(), level.get_target())
+    }
+
+    fn player(name: &str) -> &str {
+        match name.trim() {
+            "" => ANONYMOUS,
+            name => name,
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        let json = serde_json::to_string(&self.progress).unwrap();
+        let mut file = File::create(JSON_FILE_PATH).unwrap();
+        file.write_all(json.as_bytes()).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test_campaign {
+    use super::{Campaign, Progress, Target};
+    use crate::core::{Board, Direction, Game};
+    use std::mem::ManuallyDrop;
+
+    #[test]
+    fn levels_unlock_in_order() {
+        let boards = [
+            Board::new("first".to_string(), 9, Vec::new()),
+            Board::new("second".to_string(), 11, Vec::new()),
+        ];
+        let campaign = Campaign::from_boards(&boards);
+        let levels = campaign.get_levels();
+
+        assert_eq!(levels.len(), 6);
+        assert_eq!(levels[1].get_board().get_name(), "second");
+        assert_eq!(levels[2].get_target(), Target::Time(400));
+
+        assert!(campaign.is_unlocked(0, 0));
+        assert!(!campaign.is_unlocked(1, 0));
+        assert!(campaign.is_unlocked(3, 3));
+        assert!(!campaign.is_unlocked(6, 6));
+    }
+
+    #[test]
+    fn progress_follows_the_boards() {
+        let first = Board::new("first".to_string(), 9, Vec::new());
+        let second = Board::new("second".to_string(), 11, Vec::new());
+        let campaign = Campaign::from_boards(&[first.clone(), second.clone()]);
+        // never dropped, so that it does not write over the real progress
+        let mut progress = ManuallyDrop::new(Progress {
+            progress: Default::default(),
+        });
+
+        progress.complete("ali", &campaign.get_levels()[0]);
+        progress.complete("ali", &campaign.get_levels()[1]);
+        progress.complete("ali", &campaign.get_levels()[1]);
+        assert_eq!(progress.get_completed("ali", &campaign), 2);
+        assert_eq!(progress.get_completed(" ", &campaign), 0);
+
+        // a board added in front leaves the levels played on the others done
+        let added = Board::new("added".to_string(), 7, Vec::new());
+        let campaign = Campaign::from_boards(&[added, first, second]);
+        assert_eq!(progress.get_completed("ali", &campaign), 0);
+        assert!(progress.is_completed("ali", &campaign.get_levels()[1]));
+        assert!(!progress.is_completed("ali", &campaign.get_levels()[3]));
+    }
+
+    #[test]
+    fn targets_follow_the_game() {
+        let board = Board::new("test".to_string(), 9, Vec::new());
+        let mut game = Game::with_seed(&board, 3, 1);
+
+        assert!(Target::Length(3).is_met(&game));
+        assert!(!Target::Score(1).is_met(&game));
+        assert!(!Target::Time(2).is_met(&game));
+
+        game.rotation(Direction::Up);
+        game.walk();
+        game.walk();
+        assert!(Target::Time(2).is_met(&game));
+    }
+}
@@ -0,0 +1,50 @@
+use super::point::Point;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum DeathCause {
+    Wall,
+    Body,
+    /// Ran into another snake's body.
+    Snake,
+    /// Two heads entered the same cell.
+    HeadOn,
+    /// The player left the game.
+    Left,
+    /// Ran into a moving obstacle or an enemy, or was caught by one.
+    Enemy,
+}
+
+/// What ended a game and where.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Death {
+    cause: DeathCause,
+    obstacle: Point,
+}
+
+impl Death {
+    pub fn new(cause: DeathCause, obstacle: Point) -> Self {
+        Self { cause, obstacle }
+    }
+
+    pub fn get_cause(&self) -> DeathCause {
+        self.cause
+    }
+
+    /// The cell the head tried to move into.
+    pub fn get_obstacle(&self) -> &Point {
+        &self.obstacle
+    }
+
+    pub fn describe(&self) -> String {
+        match self.cause {
+            DeathCause::Wall => "The snake ran into a wall.".to_string(),
+            DeathCause::Body => "The snake bit its own body.".to_string(),
+            DeathCause::Snake => "The snake ran into another snake.".to_string(),
+            DeathCause::HeadOn => "The snake crashed head-on into another snake.".to_string(),
+            DeathCause::Left => "The player left the game.".to_string(),
+            DeathCause::Enemy => "The snake was hit by something on the move.".to_string(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(PartialEq, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
 pub enum Direction {
     Up,
     Down,
@@ -17,6 +19,17 @@ impl Direction {
                 | (Direction::Right, Direction::Left)
         )
     }
+
+    /// The way back; `None` has none.
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::None => Direction::None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use super::{point::Point, Direction};
+
+/// How an entity of a board gets around.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Motion {
+    /// Walks these cells in order to the last one and back again.
+    Patrol(Vec<Point>),
+    /// Goes straight on and turns around when something is in the way.
+    Bounce(Direction),
+    /// Heads for the nearest snake.
+    Chase,
+}
+
+/// Something on a board that moves on its own and kills the snakes it
+/// runs into: a moving obstacle, or an enemy when it chases them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entity {
+    /// Where the entity starts.
+    point: Point,
+    motion: Motion,
+}
+
+impl Entity {
+    /// An obstacle walking `path` back and forth, starting on its first cell.
+    pub fn patrol(path: Vec<Point>) -> Option<Self> {
+        Some(Self {
+            point: path.first()?.clone(),
+            motion: Motion::Patrol(path),
+        })
+    }
+
+    pub fn bounce(point: Point, direction: Direction) -> Self {
+        Self {
+            point,
+            motion: Motion::Bounce(direction),
+        }
+    }
+
+    pub fn chase(point: Point) -> Self {
+        Self {
+            point,
+            motion: Motion::Chase,
+        }
+    }
+
+    pub fn get_point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn get_motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    pub fn get_glyph(&self) -> &'static str {
+        match self.motion {
+            Motion::Patrol(_) | Motion::Bounce(_) => "◘◘",
+            Motion::Chase => "ЖЖ",
+        }
+    }
+}
+
+/// An entity going about during a game.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Mover {
+    pub(super) entity: Entity,
+    pub(super) point: Point,
+    /// The cell of a patrol's path the mover is on.
+    pub(super) step: usize,
+    /// Whether a patrol is on its way back to the first cell.
+    pub(super) back: bool,
+}
+
+impl Mover {
+    pub fn new(entity: &Entity) -> Self {
+        Self {
+            entity: entity.clone(),
+            point: entity.point.clone(),
+            step: 0,
+            back: false,
+        }
+    }
+
+    pub fn get_point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    /// Sends a bouncer back the way it came.
+    pub(super) fn turn_around(&mut self) {
+        if let Motion::Bounce(direction) = &mut self.entity.motion {
+            *direction = direction.opposite();
+        }
+    }
+
+    /// The cell a patrol goes to next and the step it is then on, turning
+    /// around at either end of its path.
+    pub(super) fn next_step(&self) -> Option<(usize, bool)> {
+        let Motion::Patrol(path) = &self.entity.motion else {
+            return None;
+        };
+        if path.len() < 2 {
+            return None;
+        }
+
+        let back = match self.back {
+            true => self.step > 0,
+            false => self.step + 1 >= path.len(),
+        };
+        Some(match back {
+            true => (self.step - 1, true),
+            false => (self.step + 1, false),
+        })
+    }
+}
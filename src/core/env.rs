@@ -0,0 +1,385 @@
+mod observation;
+
+pub use observation::{Observation, View};
+
+use super::{point::Point, Board, DeathCause, Direction, Game};
+
+use serde::{Deserialize, Serialize};
+
+/// What an agent does before a step: head somewhere on the board, or turn
+/// relative to where the snake heads, which suits an egocentric `View`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Straight,
+    TurnLeft,
+    TurnRight,
+}
+
+impl Action {
+    fn direction(self, heading: Direction) -> Option<Direction> {
+        let turns = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ];
+        let turn = |by: usize| {
+            let at = turns.iter().position(|d| *d == heading)?;
+            Some(turns[(at + by) % turns.len()])
+        };
+
+        match self {
+            Self::Up => Some(Direction::Up),
+            Self::Down => Some(Direction::Down),
+            Self::Left => Some(Direction::Left),
+            Self::Right => Some(Direction::Right),
+            Self::Straight => None,
+            Self::TurnRight => turn(1),
+            Self::TurnLeft => turn(3),
+        }
+    }
+}
+
+/// What each thing that can happen on a step is worth to the agent.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Reward {
+    pub food: f32,
+    pub death: f32,
+    pub cleared: f32,
+    /// Given on every step, usually a small penalty against dawdling.
+    pub step: f32,
+    /// Given for a step towards the food, and taken for a step away from it.
+    pub closer: f32,
+}
+
+impl Default for Reward {
+    fn default() -> Self {
+        Self {
+            food: 1.0,
+            death: -1.0,
+            cleared: 10.0,
+            step: 0.0,
+            closer: 0.0,
+        }
+    }
+}
+
+/// How an `Env` plays its games.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    pub length: u16,
+    /// Steps after which an episode is cut short.
+    pub max_ticks: u32,
+    pub view: View,
+    pub reward: Reward,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            length: 3,
+            max_ticks: 10_000,
+            view: View::Full,
+            reward: Reward::default(),
+        }
+    }
+}
+
+/// How an episode stands after a step.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Info {
+    pub seed: u64,
+    pub score: u16,
+    pub ticks: u32,
+    pub ate: bool,
+    pub death: Option<DeathCause>,
+    pub cleared: bool,
+    /// Whether the episode ended because it reached `Config::max_ticks`.
+    pub truncated: bool,
+}
+
+/// What a step returns.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Step {
+    pub observation: Observation,
+    pub reward: f32,
+    pub done: bool,
+    pub info: Info,
+}
+
+/// A single player game for training agents: `reset` starts an episode and
+/// every `step` plays one walk of it.
+pub struct Env<'a> {
+    board: &'a Board,
+    config: Config,
+    game: Game<'a>,
+}
+
+impl<'a> Env<'a> {
+    pub fn new(board: &'a Board, config: Config) -> Self {
+        Self {
+            board,
+            config,
+            game: Game::with_seed(board, config.length, 0),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_game(&self) -> &Game<'a> {
+        &self.game
+    }
+
+    /// Starts the episode of `seed` and returns what the agent sees first.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.game = Game::with_seed(self.board, self.config.length, seed);
+
+        self.observe()
+    }
+
+    /// Plays `action` for one walk. A step after the episode is done
+    /// changes nothing and is worth nothing.
+    pub fn step(&mut self, action: Action) -> Step {
+        let reward = &self.config.reward;
+
+        if self.is_done() {
+            return Step {
+                observation: self.observe(),
+                reward: 0.0,
+                done: true,
+                info: self.info(false),
+            };
+        }
+
+        let player = &self.game.get_players()[0];
+        if let Some(direction) = action.direction(player.get_direction()) {
+            self.game.rotation(direction);
+        }
+
+        let score = self.game.get_score();
+        let before = self.distance_to_food();
+        self.game.walk();
+        let ate = self.game.get_score() > score;
+
+        let mut value = reward.step;
+        if ate {
+            value += reward.food;
+        } else if let (Some(before), Some(after)) = (before, self.distance_to_food()) {
+            value += reward.closer * (before as f32 - after as f32).signum();
+        }
+        if self.game.get_death().is_some() {
+            value += reward.death;
+        }
+        if self.game.is_cleared() {
+            value += reward.cleared;
+        }
+
+        Step {
+            observation: self.observe(),
+            reward: value,
+            done: self.is_done(),
+            info: self.info(ate),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.game.is_over() || self.game.get_ticks() >= self.config.max_ticks
+    }
+
+    fn observe(&self) -> Observation {
+        Observation::new(&self.game, 0, self.config.view)
+    }
+
+    fn info(&self, ate: bool) -> Info {
+        Info {
+            seed: self.game.get_seed(),
+            score: self.game.get_score(),
+            ticks: self.game.get_ticks(),
+            ate,
+            death: self.game.get_death().map(|death| death.get_cause()),
+            cleared: self.game.is_cleared(),
+            truncated: !self.game.is_over() && self.game.get_ticks() >= self.config.max_ticks,
+        }
+    }
+
+    /// Steps from the head to the food, the edges of the board wrapping around.
+    fn distance_to_food(&self) -> Option<i16> {
+        let head: &Point = self.game.get_players()[0].get_snake().front()?;
+        let food = self.game.get_food();
+
+        Some(head.distance(food, self.board.get_size()))
+    }
+}
+
+/// Many independent `Env`s on the same board stepped together.
+///
+/// An env whose episode is done starts the next one right away, with the
+/// next seed nobody played yet: its step still reports `done` and the
+/// `Info` of the finished episode, but the observation of the new one.
+pub struct VecEnv<'a> {
+    envs: Vec<Env<'a>>,
+    next_seed: u64,
+}
+
+impl<'a> VecEnv<'a> {
+    pub fn new(board: &'a Board, config: Config, count: usize) -> Self {
+        Self {
+            envs: (0..count).map(|_| Env::new(board, config)).collect(),
+            next_seed: 0,
+        }
+    }
+
+    /// Starts every env over, the first with `seed` and the others with the
+    /// seeds after it.
+    pub fn reset(&mut self, seed: u64) -> Vec<Observation> {
+        self.next_seed = seed;
+
+        self.envs
+            .iter_mut()
+            .map(|env| {
+                let observation = env.reset(self.next_seed);
+                self.next_seed = self.next_seed.wrapping_add(1);
+                observation
+            })
+            .collect()
+    }
+
+    /// Steps the `i`-th env with the `i`-th action. Envs without an action
+    /// go straight on.
+    pub fn step(&mut self, actions: &[Action]) -> Vec<Step> {
+        self.envs
+            .iter_mut()
+            .enumerate()
+            .map(|(index, env)| {
+                let action = actions.get(index).copied().unwrap_or(Action::Straight);
+                let mut step = env.step(action);
+                if step.done {
+                    step.observation = env.reset(self.next_seed);
+                    self.next_seed = self.next_seed.wrapping_add(1);
+                }
+                step
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test_env {
+    use super::{observation::Channel, Action, Config, Env, Reward, VecEnv, View};
+    use crate::core::{Board, DeathCause, Direction, Entity, Point, Tile, Wall};
+
+    #[test]
+    fn observes_the_board() {
+        let board = Board::new("test".to_string(), 7, Vec::from([Wall::new(0, 0)]));
+        let mut env = Env::new(&board, Config::default());
+        let observation = env.reset(2);
+        let food = env.get_game().get_food().clone();
+
+        assert_eq!(observation.get_size(), 7);
+        assert_eq!(observation.get_data().len(), 8 * 7 * 7);
+        assert_eq!(observation.get(Channel::Walls, 0, 0), 1.0);
+        assert_eq!(observation.get(Channel::Head, 3, 4), 1.0);
+        assert_eq!(observation.get(Channel::Body, 3, 4), 0.0);
+        assert_eq!(observation.get(Channel::Body, 3, 2), 1.0);
+        assert_eq!(
+            observation.get(Channel::Food, food.get_x() as usize, food.get_y() as usize),
+            1.0
+        );
+    }
+
+    #[test]
+    fn observes_what_else_is_on_the_board() {
+        let mut board = Board::new("test".to_string(), 7, Vec::new());
+        board.set_tile(Point::new(0, 1), Tile::Breakable).unwrap();
+        board.set_tile(Point::new(0, 3), Tile::Portal(0)).unwrap();
+        board.set_tile(Point::new(6, 3), Tile::Portal(0)).unwrap();
+        board.add_entity(Entity::bounce(Point::new(5, 0), Direction::Right));
+        let mut env = Env::new(&board, Config::default());
+        let observation = env.reset(2);
+
+        assert_eq!(observation.get(Channel::Breakable, 0, 1), 1.0);
+        assert_eq!(observation.get(Channel::Walls, 0, 1), 0.0);
+        assert_eq!(observation.get(Channel::Portals, 0, 3), 1.0);
+        assert_eq!(observation.get(Channel::Portals, 6, 3), 1.0);
+        assert_eq!(observation.get(Channel::Entities, 5, 0), 1.0);
+    }
+
+    #[test]
+    fn egocentric_view_heads_up() {
+        let board = Board::new("test".to_string(), 9, Vec::from([Wall::new(4, 7)]));
+        let config = Config {
+            view: View::Egocentric { radius: 2 },
+            ..Config::default()
+        };
+        let mut env = Env::new(&board, config);
+
+        // heading right: the wall two cells ahead is two rows above the head
+        let observation = env.reset(1);
+        assert_eq!(observation.get_size(), 5);
+        assert_eq!(observation.get(Channel::Head, 2, 2), 1.0);
+        assert_eq!(observation.get(Channel::Walls, 0, 2), 1.0);
+        assert_eq!(observation.get(Channel::Body, 3, 2), 1.0);
+
+        // after turning left the wall is behind on the right
+        let step = env.step(Action::TurnLeft);
+        assert_eq!(step.observation.get(Channel::Walls, 3, 4), 1.0);
+        assert_eq!(step.observation.get(Channel::Body, 3, 2), 1.0);
+    }
+
+    #[test]
+    fn rewards_and_ends_episodes() {
+        let board = Board::new("test".to_string(), 9, Vec::from([Wall::new(4, 7)]));
+        let config = Config {
+            reward: Reward {
+                step: -0.25,
+                ..Reward::default()
+            },
+            ..Config::default()
+        };
+        let mut env = Env::new(&board, config);
+        env.reset(1);
+
+        env.step(Action::Straight);
+        let step = env.step(Action::Straight);
+        assert!(step.done);
+        assert_eq!(step.reward, -1.25);
+        assert_eq!(step.info.death, Some(DeathCause::Wall));
+
+        let again = env.step(Action::Up);
+        assert!(again.done);
+        assert_eq!(again.reward, 0.0);
+    }
+
+    #[test]
+    fn batches_reset_finished_envs() {
+        let board = Board::new("test".to_string(), 9, Vec::new());
+        let config = Config {
+            max_ticks: 3,
+            ..Config::default()
+        };
+        let mut envs = VecEnv::new(&board, config, 3);
+        envs.reset(10);
+
+        for _ in 0..2 {
+            assert!(envs.step(&[]).iter().all(|step| !step.done));
+        }
+        let steps = envs.step(&[Action::Up, Action::Down]);
+
+        assert!(steps.iter().all(|step| step.done && step.info.truncated));
+        assert_eq!(
+            steps.iter().map(|step| step.info.seed).collect::<Vec<_>>(),
+            Vec::from([10, 11, 12])
+        );
+        assert_eq!(
+            envs.envs
+                .iter()
+                .map(|env| env.get_game().get_seed())
+                .collect::<Vec<_>>(),
+            Vec::from([13, 14, 15])
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use super::super::{point::Point, Direction, FoodKind, Game, Tile};
+
+use serde::{Deserialize, Serialize};
+
+/// The planes of an `Observation`, in the order they are stored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Channel {
+    /// Walls and survival obstacles, which end the game when run into.
+    Walls,
+    /// Every snake cell but the agent's head.
+    Body,
+    Head,
+    Food,
+    /// Breakable walls that have not given way yet.
+    Breakable,
+    /// Moving obstacles and enemies.
+    Entities,
+    Poison,
+    Portals,
+}
+
+impl Channel {
+    pub const ALL: [Channel; 8] = [
+        Self::Walls,
+        Self::Body,
+        Self::Head,
+        Self::Food,
+        Self::Breakable,
+        Self::Entities,
+        Self::Poison,
+        Self::Portals,
+    ];
+}
+
+/// What part of the board an agent sees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum View {
+    /// The whole board as it is.
+    Full,
+    /// The cells up to `radius` away from the head, turned so the snake
+    /// always heads up. The board wraps around, so the view does too.
+    Egocentric { radius: u16 },
+}
+
+/// The board as planes of ones and zeros, one per `Channel`, each `size`
+/// rows of `size` cells.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Observation {
+    size: usize,
+    data: Vec<f32>,
+}
+
+impl Observation {
+    pub(super) fn new(game: &Game, player: usize, view: View) -> Self {
+        let board = game.get_board();
+        let table_size = board.get_size();
+        let snake = game.get_players()[player].get_snake();
+        let head = snake.front();
+
+        let size = match view {
+            View::Full => table_size as usize,
+            View::Egocentric { radius } => radius as usize * 2 + 1,
+        };
+        let mut observation = Self {
+            size,
+            data: vec![0.0; Channel::ALL.len() * size * size],
+        };
+
+        // where each cell of the board is in the view, if it is
+        let locate = |point: &Point| -> Option<(usize, usize)> {
+            match view {
+                View::Full => Some((point.get_x() as usize, point.get_y() as usize)),
+                View::Egocentric { radius } => {
+                    let head = head?;
+                    let heading = game.get_players()[player].get_direction();
+                    let (dx, dy) = head.offset(point, table_size);
+                    // turn the board so that `heading` points up
+                    let (row, column) = match heading {
+                        Direction::Up | Direction::None => (dx, dy),
+                        Direction::Down => (-dx, -dy),
+                        Direction::Left => (dy, -dx),
+                        Direction::Right => (-dy, dx),
+                    };
+                    let radius = radius as i16;
+
+                    (row.abs() <= radius && column.abs() <= radius)
+                        .then(|| ((row + radius) as usize, (column + radius) as usize))
+                }
+            }
+        };
+
+        for wall in board.into_iter().chain(game.get_obstacles()) {
+            observation.mark(Channel::Walls, locate(wall));
+        }
+        for placed in board.get_tiles() {
+            let point = placed.get_point();
+            match placed.get_tile() {
+                Tile::Breakable if game.is_wall(point) => {
+                    observation.mark(Channel::Breakable, locate(point))
+                }
+                Tile::Portal(_) => observation.mark(Channel::Portals, locate(point)),
+                _ => {}
+            }
+        }
+        for mover in game.get_movers() {
+            observation.mark(Channel::Entities, locate(mover.get_point()));
+        }
+        for item in game.get_items() {
+            if item.get_kind() == FoodKind::Poison {
+                observation.mark(Channel::Poison, locate(item.get_point()));
+            }
+        }
+        for (index, other) in game.get_players().iter().enumerate() {
+            if !other.is_alive() {
+                continue;
+            }
+            let skip = (index == player) as usize;
+            for point in other.get_snake().iter().skip(skip) {
+                observation.mark(Channel::Body, locate(point));
+            }
+        }
+        if let Some(head) = head {
+            observation.mark(Channel::Head, locate(head));
+        }
+        observation.mark(Channel::Food, locate(game.get_food()));
+
+        observation
+    }
+
+    fn mark(&mut self, channel: Channel, cell: Option<(usize, usize)>) {
+        if let Some((row, column)) = cell {
+            self.data[(channel as usize * self.size + row) * self.size + column] = 1.0;
+        }
+    }
+
+    /// How many rows and columns every plane has.
+    #[cfg(test)]
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// All planes one after another, each row by row.
+    #[cfg(test)]
+    pub fn get_data(&self) -> &[f32] {
+        &self.data
+    }
+
+    #[cfg(test)]
+    pub fn get(&self, channel: Channel, row: usize, column: usize) -> f32 {
+        self.data[(channel as usize * self.size + row) * self.size + column]
+    }
+}
@@ -0,0 +1,222 @@
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::{Board, Genome, Neural, Simulation};
+
+const SNAKE_LENGTH: u16 = 3;
+/// How many genomes compete for every parent.
+const TOURNAMENT: usize = 3;
+
+/// How a `Trainer` breeds and tests its genomes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Training {
+    pub population: usize,
+    /// The best genomes of a generation that go on to the next unchanged.
+    pub elite: usize,
+    /// Games every genome plays on every board to be judged.
+    pub games: usize,
+    pub max_ticks: u32,
+    /// How likely each weight of a child is to change.
+    pub mutation_rate: f64,
+    /// How far a weight changes at most.
+    pub mutation_scale: f32,
+}
+
+impl Default for Training {
+    fn default() -> Self {
+        Self {
+            population: 50,
+            elite: 5,
+            games: 5,
+            max_ticks: 2_000,
+            mutation_rate: 0.1,
+            mutation_scale: 0.5,
+        }
+    }
+}
+
+/// How a generation did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generation {
+    pub index: u32,
+    pub best: f64,
+    pub mean: f64,
+}
+
+/// Evolves the genomes of `Neural` bots: every generation plays the same
+/// seeded games, and the best of it breed the next.
+pub struct Trainer<'a> {
+    boards: Vec<&'a Board>,
+    training: Training,
+    rng: ChaCha8Rng,
+    generation: u32,
+    population: Vec<Genome>,
+    best: Option<(Genome, f64)>,
+}
+
+impl<'a> Trainer<'a> {
+    /// A trainer starting from random genomes, determined by `seed`.
+    pub fn new(boards: Vec<&'a Board>, training: Training, seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let population = (0..training.population.max(1))
+            .map(|_| Genome::random(&mut rng))
+            .collect();
+
+        Self {
+            boards,
+            training,
+            rng,
+            generation: 0,
+            population,
+            best: None,
+        }
+    }
+
+    /// A trainer starting from `genome` and mutations of it, to go on with a
+    /// saved one.
+    pub fn from_genome(
+        boards: Vec<&'a Board>,
+        training: Training,
+        seed: u64,
+        genome: Genome,
+    ) -> Self {
+        let mut trainer = Self::new(boards, training, seed);
+        for (index, slot) in trainer.population.iter_mut().enumerate() {
+            *slot = genome.clone();
+            if index > 0 {
+                slot.mutate(
+                    training.mutation_rate,
+                    training.mutation_scale,
+                    &mut trainer.rng,
+                );
+            }
+        }
+
+        trainer
+    }
+
+    /// The genome that did best in any generation so far and its fitness.
+    pub fn get_best(&self) -> Option<&(Genome, f64)> {
+        self.best.as_ref()
+    }
+
+    /// Judges the current population and breeds the next one from it.
+    pub fn evolve(&mut self) -> Generation {
+        let seed: u64 = self.rng.gen();
+        let mut ranked: Vec<(Genome, f64)> = self
+            .population
+            .drain(..)
+            .map(|genome| {
+                let fitness = Self::fitness(&self.boards, &self.training, &genome, seed);
+                (genome, fitness)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let generation = Generation {
+            index: self.generation,
+            best: ranked[0].1,
+            mean: ranked.iter().map(|(_, fitness)| fitness).sum::<f64>() / ranked.len() as f64,
+        };
+        if self
+            .best
+            .as_ref()
+            .is_none_or(|(_, best)| ranked[0].1 > *best)
+        {
+            self.best = Some(ranked[0].clone());
+        }
+
+        let elite = self.training.elite.clamp(1, ranked.len());
+        self.population = ranked[..elite].iter().map(|(g, _)| g.clone()).collect();
+        while self.population.len() < self.training.population {
+            let mother = Self::select(&ranked, &mut self.rng);
+            let father = Self::select(&ranked, &mut self.rng);
+            let mut child = mother.crossover(father, &mut self.rng);
+            child.mutate(
+                self.training.mutation_rate,
+                self.training.mutation_scale,
+                &mut self.rng,
+            );
+            self.population.push(child);
+        }
+        self.generation += 1;
+
+        generation
+    }
+
+    /// The food eaten per game, with a little extra for lasting longer to
+    /// tell apart genomes that never eat.
+    fn fitness(boards: &[&Board], training: &Training, genome: &Genome, seed: u64) -> f64 {
+        let total: f64 = boards
+            .iter()
+            .map(|board| {
+                let simulation = Simulation::new(board, SNAKE_LENGTH, training.max_ticks);
+                let report = simulation.run(training.games, seed, |_| {
+                    Box::new(Neural::new(genome.clone()))
+                });
+
+                match (report.get_scores(), report.get_survival()) {
+                    (Some(scores), Some(survival)) => {
+                        scores.mean + survival.mean / training.max_ticks as f64
+                    }
+                    _ => 0.0,
+                }
+            })
+            .sum();
+
+        total / boards.len().max(1) as f64
+    }
+
+    fn select<'r>(ranked: &'r [(Genome, f64)], rng: &mut ChaCha8Rng) -> &'r Genome {
+        ranked
+            .choose_multiple(rng, TOURNAMENT)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(genome, _)| genome)
+            .unwrap_or(&ranked[0].0)
+    }
+}
+
+#[cfg(test)]
+mod test_evolution {
+    use super::{Trainer, Training};
+    use crate::core::Board;
+
+    fn training() -> Training {
+        Training {
+            population: 12,
+            elite: 2,
+            games: 2,
+            max_ticks: 200,
+            ..Training::default()
+        }
+    }
+
+    #[test]
+    fn the_best_genome_only_gets_better() {
+        let board = Board::new("test".to_string(), 8, Vec::new());
+        let mut trainer = Trainer::new(Vec::from([&board]), training(), 3);
+
+        let mut best = f64::MIN;
+        for index in 0..4 {
+            let generation = trainer.evolve();
+            assert_eq!(generation.index, index);
+            assert!(generation.best >= generation.mean);
+
+            let (_, fitness) = trainer.get_best().unwrap();
+            assert!(*fitness >= best);
+            best = *fitness;
+        }
+        assert!(best > 1.0);
+    }
+
+    #[test]
+    fn same_seed_same_training() {
+        let board = Board::new("test".to_string(), 8, Vec::new());
+        let mut first = Trainer::new(Vec::from([&board]), training(), 5);
+        let mut second = Trainer::new(Vec::from([&board]), training(), 5);
+
+        assert_eq!(first.evolve(), second.evolve());
+        assert_eq!(first.evolve(), second.evolve());
+        assert_eq!(first.get_best(), second.get_best());
+    }
+}
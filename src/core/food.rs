@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use super::point::Point;
+
+/// The kinds of food a snake can eat.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodKind {
+    /// Worth a point and a cell; there is always one on the board.
+    Normal,
+    /// Worth more than normal food, but only for a short while.
+    Bonus,
+    /// Worth nothing and takes cells away.
+    Poison,
+    /// Worth a point and grows the snake by several cells.
+    Golden,
+}
+
+impl FoodKind {
+    /// The kinds that show up besides the normal food.
+    pub const EXTRAS: [FoodKind; 3] = [FoodKind::Bonus, FoodKind::Poison, FoodKind::Golden];
+
+    pub fn get_points(&self) -> u16 {
+        match self {
+            FoodKind::Normal | FoodKind::Golden => 1,
+            FoodKind::Bonus => 3,
+            FoodKind::Poison => 0,
+        }
+    }
+
+    /// How many cells the snake grows by, or loses when negative.
+    pub fn get_growth(&self) -> i16 {
+        match self {
+            FoodKind::Normal | FoodKind::Bonus => 1,
+            FoodKind::Poison => -2,
+            FoodKind::Golden => 3,
+        }
+    }
+
+    /// Walks the food stays on the board before it goes away, if it does.
+    pub fn get_lifetime(&self) -> Option<u32> {
+        match self {
+            FoodKind::Normal => None,
+            FoodKind::Bonus => Some(40),
+            FoodKind::Poison => Some(80),
+            FoodKind::Golden => Some(60),
+        }
+    }
+}
+
+/// A piece of food lying on the board.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Food {
+    point: Point,
+    kind: FoodKind,
+    /// Walks left before it goes away, for food that does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ticks_left: Option<u32>,
+}
+
+impl Food {
+    pub fn new(point: Point, kind: FoodKind) -> Self {
+        Self {
+            point,
+            kind,
+            ticks_left: kind.get_lifetime(),
+        }
+    }
+
+    pub fn get_point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn get_kind(&self) -> FoodKind {
+        self.kind
+    }
+
+    pub fn get_ticks_left(&self) -> Option<u32> {
+        self.ticks_left
+    }
+
+    /// Counts one walk down and returns whether the food is still there.
+    pub(super) fn age(&mut self) -> bool {
+        match &mut self.ticks_left {
+            Some(ticks) => {
+                *ticks = ticks.saturating_sub(1);
+                *ticks > 0
+            }
+            None => true,
+        }
+    }
+}
+
+/// How likely each extra kind of food is to show up on a walk of a board.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(default)]
+pub struct FoodRates {
+    pub bonus: f64,
+    pub poison: f64,
+    pub golden: f64,
+}
+
+impl FoodRates {
+    pub fn get(&self, kind: FoodKind) -> f64 {
+        match kind {
+            FoodKind::Normal => 0.0,
+            FoodKind::Bonus => self.bonus,
+            FoodKind::Poison => self.poison,
+            FoodKind::Golden => self.golden,
+        }
+    }
+
+    /// Whether only the normal food ever shows up, as on the classic boards.
+    pub fn is_none(&self) -> bool {
+        FoodKind::EXTRAS.iter().all(|kind| self.get(*kind) <= 0.0)
+    }
+}
@@ -1,82 +1,828 @@
-use rand::Rng;
-use std::collections::LinkedList;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-use super::{point::Point, Board, Direction};
+use super::{
+    player::{Player, Snake},
+    point::Point,
+    power_up, Board, Death, DeathCause, Direction, Food, FoodKind, GameMode, Motion, Mover,
+    PowerUp, PowerUpKind, Replay, SavedGame, Snapshot, Spawn, Tile,
+};
 
-type Snake = LinkedList<Point>;
-type Food = Point;
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
 
+/// How close to a head a survival obstacle may appear at the nearest.
+const OBSTACLE_DISTANCE: i16 = 3;
+/// Random cells tried for an obstacle before giving up on this one.
+const OBSTACLE_ATTEMPTS: usize = 100;
+/// Bonus, poison and golden food on the board at most at the same time.
+const MAX_EXTRA_FOOD: usize = 3;
+/// Power-ups on the board at most at the same time.
+const MAX_POWER_UPS: usize = 2;
+/// Walks an entity of the board takes for each of its moves, so snakes can
+/// outrun it.
+const ENTITY_INTERVAL: u32 = 2;
+
+#[derive(Clone)]
 pub struct Game<'a> {
     board: &'a Board,
-    snake: Snake,
-    food: Food,
-    score: u16,
-    direction: Direction,
+    players: Vec<Player>,
+    food: Point,
+    /// Food besides the normal one, each piece going away on its own time.
+    items: Vec<Food>,
+    /// Power-ups lying on the board, waiting to be picked up.
+    power_ups: Vec<PowerUp>,
+    length: u16,
+    seed: u64,
+    rng: ChaCha8Rng,
+    ticks: u32,
+    replay: Replay,
+    open_cells: usize,
+    cleared: bool,
+    mode: GameMode,
+    /// Cells that became walls during the game.
+    obstacles: Vec<Point>,
+    /// Breakable walls of the board that gave way.
+    broken: Vec<Point>,
+    /// The moving obstacles and enemies of the board, where they are now.
+    movers: Vec<Mover>,
 }
 
 impl<'a> Game<'a> {
     pub fn new(board: &'a Board, length: u16) -> Self {
-        let snake = Self::create_snake(board.get_size(), length);
-        let food = Self::find_lunch_point(&snake, board);
-
-        Self {
-            food,
-            snake,
-            score: 0,
-            direction: Direction::Right,
+        Self::with_seed(board, length, rand::random())
+    }
+
+    /// A game whose food placement is fully determined by `seed`.
+    pub fn with_seed(board: &'a Board, length: u16, seed: u64) -> Self {
+        Self::with_players(board, length, seed, 1)
+    }
+
+    /// A game of `players` snakes sharing the board, each starting at its
+    /// spawn point of the board.
+    pub fn with_players(board: &'a Board, length: u16, seed: u64, players: usize) -> Self {
+        Self::with_mode(board, length, seed, players, GameMode::Classic)
+    }
+
+    /// A game of `players` snakes playing by the rules of `mode`.
+    pub fn with_mode(
+        board: &'a Board,
+        length: u16,
+        seed: u64,
+        players: usize,
+        mode: GameMode,
+    ) -> Self {
+        let rng = ChaCha8Rng::seed_from_u64(seed);
+        let players: Vec<Player> = (0..players.max(1))
+            .map(|index| {
+                let spawn = board.get_spawn(index, players);
+                let snake = Self::spawn_snake(&spawn, board.get_size(), length);
+                Player::new(snake, spawn.get_direction())
+            })
+            .collect();
+
+        let mut game = Self {
+            food: players[0].get_snake_head().clone(),
+            items: Vec::new(),
+            power_ups: Vec::new(),
+            replay: Replay::new(seed, length, players.len(), mode),
+            players,
             board,
+            length,
+            seed,
+            rng,
+            ticks: 0,
+            open_cells: board.count_open_cells(),
+            cleared: false,
+            mode,
+            obstacles: Vec::new(),
+            broken: Vec::new(),
+            movers: board.get_entities().iter().map(Mover::new).collect(),
+        };
+        match game.find_free_cell() {
+            Some(food) => game.food = food,
+            None => game.cleared = true,
         }
+
+        game
+    }
+
+    /// A game showing `snapshot`, as sent by the game that took it.
+    pub fn from_snapshot(board: &'a Board, snapshot: Snapshot) -> Self {
+        let mut game = Self::with_players(board, 1, 0, snapshot.get_players().len());
+        game.players = snapshot.get_players().to_vec();
+        game.food = snapshot.get_food().clone();
+        game.items = snapshot.get_items().to_vec();
+        game.power_ups = snapshot.get_power_ups().to_vec();
+        game.broken = snapshot.get_broken().to_vec();
+        game.movers = snapshot.get_movers().to_vec();
+        game.ticks = snapshot.get_ticks();
+        game
+    }
+
+    /// Everything about the game to go on with it later through `resume`.
+    pub fn save(&self) -> SavedGame {
+        SavedGame {
+            board: self.board.get_name().to_string(),
+            players: self.players.clone(),
+            food: self.food.clone(),
+            items: self.items.clone(),
+            power_ups: self.power_ups.clone(),
+            length: self.length,
+            seed: self.seed,
+            rng: self.rng.clone(),
+            ticks: self.ticks,
+            replay: self.replay.clone(),
+            cleared: self.cleared,
+            mode: self.mode,
+            obstacles: self.obstacles.clone(),
+            broken: self.broken.clone(),
+            movers: self.movers.clone(),
+        }
+    }
+
+    /// The game `saved` stopped at, on `board`, which has to be the board it
+    /// was saved on.
+    pub fn resume(board: &'a Board, saved: SavedGame) -> Result<Self, String> {
+        let size = board.get_size() as i16;
+        let on_board = |point: &Point| {
+            (0..size).contains(&point.get_x()) && (0..size).contains(&point.get_y())
+        };
+
+        if saved.board != board.get_name() {
+            return Err(format!(
+                "The game was saved on the board '{}', not '{}'.",
+                saved.board,
+                board.get_name()
+            ));
+        }
+        if saved.players.is_empty()
+            || !saved
+                .players
+                .iter()
+                .flat_map(|p| p.get_snake())
+                .all(on_board)
+            || !on_board(&saved.food)
+            || !saved.items.iter().all(|item| on_board(item.get_point()))
+            || !saved.power_ups.iter().all(|p| on_board(p.get_point()))
+            || !saved.movers.iter().all(|m| on_board(m.get_point()))
+            || !saved.obstacles.iter().all(on_board)
+            || !saved
+                .broken
+                .iter()
+                .all(|point| board.get_tile(point) == Tile::Breakable)
+        {
+            return Err(format!(
+                "The saved game does not fit on the board '{}'.",
+                board.get_name()
+            ));
+        }
+        let broken = &saved.broken;
+        if (1..broken.len()).any(|index| broken[..index].contains(&broken[index])) {
+            return Err("The saved game breaks the same wall twice.".to_string());
+        }
+        let Some(open_cells) = board.count_open_cells().checked_sub(saved.obstacles.len()) else {
+            return Err(format!(
+                "The saved game has more obstacles than the board '{}' has room for.",
+                board.get_name()
+            ));
+        };
+
+        Ok(Self {
+            board,
+            players: saved.players,
+            food: saved.food,
+            items: saved.items,
+            power_ups: saved.power_ups,
+            length: saved.length,
+            seed: saved.seed,
+            rng: saved.rng,
+            ticks: saved.ticks,
+            replay: saved.replay,
+            open_cells: open_cells + saved.broken.len(),
+            cleared: saved.cleared,
+            mode: saved.mode,
+            obstacles: saved.obstacles,
+            broken: saved.broken,
+            movers: saved.movers,
+        })
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::new(
+            self.players.clone(),
+            self.food.clone(),
+            self.items.clone(),
+            self.power_ups.clone(),
+            self.broken.clone(),
+            self.movers.clone(),
+            self.ticks,
+        )
     }
 
+    /// A fresh game on the same board with the same starting length, players
+    /// and mode.
+    pub fn restart(&self, seed: u64) -> Self {
+        Self::with_mode(self.board, self.length, seed, self.players.len(), self.mode)
+    }
+
+    pub fn get_mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// Walks left before a timed game ends, if it is timed.
+    pub fn get_ticks_left(&self) -> Option<u32> {
+        self.mode
+            .get_time_limit()
+            .map(|limit| limit.saturating_sub(self.ticks))
+    }
+
+    pub fn get_movers(&self) -> &[Mover] {
+        &self.movers
+    }
+
+    pub fn get_obstacles(&self) -> &[Point] {
+        &self.obstacles
+    }
+
+    /// Whether `point` is a wall of the board that still stands or an
+    /// obstacle added since.
+    pub fn is_wall(&self, point: &Point) -> bool {
+        (self.board.is_wall(point) && !self.broken.contains(point))
+            || self.obstacles.contains(point)
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Number of walks so far.
+    pub fn get_ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    pub fn get_replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn get_players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Why the first player's snake died, if it did.
+    pub fn get_death(&self) -> Option<&Death> {
+        self.players[0].get_death()
+    }
+
+    /// Turns the first player's snake before the next `walk`.
     pub fn rotation(&mut self, direction: Direction) {
-        if !self.direction.is_opposite(&direction) {
-            self.direction = direction;
+        self.rotate(0, direction);
+    }
+
+    /// Turns the `player`-th snake before the next `walk`.
+    pub fn rotate(&mut self, player: usize, direction: Direction) {
+        let Some(snake) = self.players.get_mut(player) else {
+            return;
+        };
+        // a snake on ice slides on the way it is going
+        if self.board.get_tile(snake.get_snake_head()) == Tile::Ice {
+            return;
+        }
+
+        if snake.is_alive() && snake.rotation(direction) {
+            self.replay.record(self.ticks, player, direction);
         }
     }
 
     pub fn get_score(&self) -> u16 {
-        self.score
+        self.players[0].get_score()
     }
 
+    pub fn get_board(&self) -> &'a Board {
+        self.board
+    }
+
+    /// Where the normal food is; there is always one until the board is full.
+    pub fn get_food(&self) -> &Point {
+        &self.food
+    }
+
+    /// The bonus, poison and golden food lying on the board.
+    pub fn get_items(&self) -> &[Food] {
+        &self.items
+    }
+
+    pub fn get_power_ups(&self) -> &[PowerUp] {
+        &self.power_ups
+    }
+
+    pub fn get_snake_head(&self) -> &Point {
+        self.players[0].get_snake_head()
+    }
+
+    /// Takes the `player`-th snake out of the game where it stands.
+    pub fn forfeit(&mut self, player: usize) {
+        let Some(snake) = self.players.get_mut(player).filter(|p| p.is_alive()) else {
+            return;
+        };
+
+        snake.death = Some(Death::new(DeathCause::Left, snake.get_snake_head().clone()));
+    }
+
+    /// Whether the snakes filled every cell that is not a wall.
+    pub fn is_cleared(&self) -> bool {
+        self.cleared
+    }
+
+    /// A single snake plays until it dies; several play until at most one is
+    /// left. Nobody plays on once the board is full or the time is up.
+    pub fn is_over(&self) -> bool {
+        let alive = self.players.iter().filter(|p| p.is_alive()).count();
+
+        if self.cleared || self.get_ticks_left() == Some(0) {
+            return true;
+        }
+
+        match self.players.len() {
+            1 => alive == 0,
+            _ => alive <= 1,
+        }
+    }
+
+    /// The last snake standing of a finished multiplayer game; `None` for a draw.
+    pub fn get_winner(&self) -> Option<usize> {
+        if self.players.len() < 2 || !self.is_over() {
+            return None;
+        }
+
+        self.players.iter().position(|p| p.is_alive())
+    }
+
+    /// Moves every living snake one cell at the same time.
+    ///
+    /// A snake dies when its head enters a wall, any snake's body (its own
+    /// included) or the cell another head enters on the same walk; in zen it
+    /// stays where it was and loses length instead. Ghosts pass through their
+    /// own body and phasing snakes through walls.
+    ///
+    /// A snake running into a breakable wall breaks it and stays where it
+    /// was; one entering mud sits out its next walk, and one entering a speed
+    /// pad gets a speed boost.
+    ///
+    /// The moving obstacles and enemies of the board move after the snakes,
+    /// every other walk, and kill a snake whose head they run into. Returns
+    /// whether the game goes on.
     pub fn walk(&mut self) -> bool {
-        let head = self.snake.front().unwrap();
+        if self.is_over() {
+            return false;
+        }
 
-        let new_head = head.get_neighbor(&self.direction, self.board.get_size());
+        self.ticks += 1;
+        self.replay.advance();
 
-        let collides_with_walls = self.board.is_wall(&new_head);
-        let collides_with_body = Self::is_snake(&self.snake, &new_head);
+        let boosted: Vec<bool> = self.players.iter().map(|p| p.is_boosted()).collect();
+        let slowed: Vec<bool> = self.players.iter().map(|p| p.is_slowed()).collect();
+        let mut picked = Vec::new();
+        if boosted.contains(&true) {
+            picked.extend(self.step(&boosted));
+        }
+        if !self.is_over() {
+            let moving: Vec<bool> = slowed
+                .iter()
+                .map(|slowed| !slowed || self.ticks.is_multiple_of(2))
+                .collect();
+            picked.extend(self.step(&moving));
+        }
 
-        if collides_with_body || collides_with_walls {
-            self.snake.pop_back();
-            self.snake.pop_front();
+        // effects picked up on this walk start counting on the next one
+        for player in self.players.iter_mut().filter(|p| p.is_alive()) {
+            power_up::expire(&mut player.effects);
+        }
+        for (index, kind) in picked {
+            power_up::stack(&mut self.players[index].effects, kind);
+        }
 
-            false
-        } else if new_head == self.food {
-            self.snake.push_front(new_head);
-            self.score += 1;
-            self.food = Self::find_lunch_point(&self.snake, self.board);
+        if self.ticks.is_multiple_of(ENTITY_INTERVAL) {
+            self.move_entities();
+        }
 
-            true
-        } else {
-            self.snake.push_front(new_head);
-            self.snake.pop_back();
+        self.items.retain_mut(|item| item.age());
+        self.power_ups.retain_mut(|power_up| power_up.age());
+        if !self.is_over() {
+            self.attract_food();
+            self.spawn_items();
+            self.spawn_power_ups();
+        }
+
+        if let Some(interval) = self.mode.get_obstacle_interval() {
+            if self.ticks.is_multiple_of(interval) && !self.is_over() {
+                self.add_obstacle();
+            }
+        }
+
+        !self.is_over()
+    }
+
+    /// Moves the snakes that are `moving` a cell on, and returns the effects
+    /// they picked up on the way with the index of their player.
+    fn step(&mut self, moving: &[bool]) -> Vec<(usize, PowerUpKind)> {
+        let table_size = self.board.get_size();
+        let headings: Vec<Direction> = self.players.iter().map(|p| p.heading).collect();
+        let heads: Vec<Option<Point>> = self
+            .players
+            .iter_mut()
+            .zip(moving)
+            .map(
+                |(player, moving)| match (moving, player.is_alive() && !player.stalled) {
+                    (false, _) => None,
+                    (true, true) => {
+                        let head = player.next_head(table_size);
+                        Some(self.board.pass(head, player.heading))
+                    }
+                    (true, false) => {
+                        player.stalled = false;
+                        None
+                    }
+                },
+            )
+            .collect();
+
+        let breaking: Vec<bool> = heads
+            .iter()
+            .zip(&self.players)
+            .map(|(head, player)| {
+                head.as_ref().is_some_and(|head| {
+                    self.board.get_tile(head) == Tile::Breakable
+                        && self.is_wall(head)
+                        && !player.has_effect(PowerUpKind::WallPhasing)
+                })
+            })
+            .collect();
+        let deaths: Vec<Option<Death>> = heads
+            .iter()
+            .enumerate()
+            .map(|(index, head)| match breaking[index] {
+                true => None,
+                false => self.collision(index, head.as_ref()?, &heads),
+            })
+            .collect();
+        let broken: Vec<Point> = heads
+            .iter()
+            .zip(&breaking)
+            .filter(|(_, breaking)| **breaking)
+            .filter_map(|(head, _)| head.clone())
+            .collect();
 
-            true
+        let mut eaten = false;
+        let mut eaten_items = Vec::new();
+        let penalty = self.mode.get_collision_penalty();
+        let mut picked = Vec::new();
+        for (index, ((((player, head), death), heading), breaking)) in self
+            .players
+            .iter_mut()
+            .zip(heads)
+            .zip(deaths)
+            .zip(headings)
+            .zip(breaking)
+            .enumerate()
+        {
+            let Some(head) = head else {
+                continue;
+            };
+            let multiplier = player.get_multiplier();
+            let moved = !breaking && death.is_none();
+
+            if breaking {
+                // the wall gives way, but the snake spends the walk on it
+                player.heading = heading;
+            } else if let (Some(_), Some(penalty)) = (&death, penalty) {
+                // the snake did not move, so turning back is still onto the neck
+                player.heading = heading;
+                let length = player.snake.len().saturating_sub(penalty).max(1);
+                player.snake.split_off(length);
+            } else if death.is_some() {
+                player.death = death;
+            } else if head == self.food {
+                player.advance(head, FoodKind::Normal.get_growth());
+                player.score += FoodKind::Normal.get_points() * multiplier;
+                eaten = true;
+            } else if let Some(item) = self.items.iter().find(|item| item.get_point() == &head) {
+                let kind = item.get_kind();
+                eaten_items.push(head.clone());
+                player.advance(head, kind.get_growth());
+                player.score += kind.get_points() * multiplier;
+            } else {
+                player.advance(head, 0);
+            }
+
+            let head = player.get_snake_head().clone();
+            if let Some(power_up) = self.power_ups.iter().position(|p| p.get_point() == &head) {
+                picked.push((index, self.power_ups.remove(power_up).get_kind()));
+            }
+            match self.board.get_tile(&head) {
+                Tile::Mud if moved => player.stalled = true,
+                Tile::SpeedPad if moved => picked.push((index, PowerUpKind::SpeedBoost)),
+                _ => {}
+            }
+        }
+
+        for point in broken {
+            if !self.broken.contains(&point) {
+                self.broken.push(point);
+                self.open_cells += 1;
+            }
+        }
+
+        self.items
+            .retain(|item| !eaten_items.contains(item.get_point()));
+
+        if eaten {
+            // extra food and power-ups give way to the normal food when nothing else is free
+            let food = match self.find_free_cell() {
+                None if !self.items.is_empty() || !self.power_ups.is_empty() => {
+                    self.items.clear();
+                    self.power_ups.clear();
+                    self.find_free_cell()
+                }
+                food => food,
+            };
+            match food {
+                Some(food) => self.food = food,
+                None => self.cleared = true,
+            }
         }
+
+        picked
     }
 
     pub fn get_table(&self) -> Vec<Vec<String>> {
         let mut result = self.board.get_table();
 
+        for broken in &self.broken {
+            result[(broken.get_x() + 1) as usize][(broken.get_y() + 1) as usize] =
+                Tile::Floor.get_glyph().to_string();
+        }
+
+        for obstacle in &self.obstacles {
+            result[(obstacle.get_x() + 1) as usize][(obstacle.get_y() + 1) as usize] =
+                "▓▓".to_string();
+        }
         Self::put_food(&mut result, &self.food);
-        Self::put_snake(&mut result, &self.snake);
+        for item in &self.items {
+            Self::put_item(&mut result, item);
+        }
+        for power_up in &self.power_ups {
+            let point = power_up.get_point();
+            result[(point.get_x() + 1) as usize][(point.get_y() + 1) as usize] =
+                format!(" {}", power_up.get_kind().get_symbol());
+        }
+        for player in &self.players {
+            self.put_snake(&mut result, player.get_snake());
+        }
+        for mover in &self.movers {
+            let point = mover.get_point();
+            result[(point.get_x() + 1) as usize][(point.get_y() + 1) as usize] =
+                mover.get_entity().get_glyph().to_string();
+        }
 
         result
     }
 }
 
 impl Game<'_> {
-    fn put_snake(result: &mut [Vec<String>], snake: &Snake) {
+    fn collision(&self, index: usize, head: &Point, heads: &[Option<Point>]) -> Option<Death> {
+        let head_on = heads
+            .iter()
+            .enumerate()
+            .any(|(other, other_head)| other != index && other_head.as_ref() == Some(head));
+        // the snakes that are out stay where they stopped, in the way of the others
+        let body = self
+            .players
+            .iter()
+            .position(|player| Self::is_snake(player.get_snake(), head));
+
+        let player = &self.players[index];
+        let blocked = self.is_wall(head) || !self.board.get_tile(head).lets_in(player.heading);
+
+        let cause = if blocked && !player.has_effect(PowerUpKind::WallPhasing) {
+            DeathCause::Wall
+        } else if self.movers.iter().any(|mover| mover.get_point() == head) {
+            DeathCause::Enemy
+        } else if head_on {
+            DeathCause::HeadOn
+        } else if body == Some(index) && !player.has_effect(PowerUpKind::Ghost) {
+            DeathCause::Body
+        } else if body.is_some_and(|other| other != index) {
+            DeathCause::Snake
+        } else {
+            return None;
+        };
+
+        Some(Death::new(cause, head.clone()))
+    }
+
+    /// Turns a random free cell away from every head into a wall. Gives up
+    /// quietly when no such cell turns up, as on a crowded board.
+    fn add_obstacle(&mut self) {
+        let size = self.board.get_size();
+
+        for _ in 0..OBSTACLE_ATTEMPTS {
+            let point = Point::new(
+                self.rng.gen_range(0..size as i16),
+                self.rng.gen_range(0..size as i16),
+            );
+            let near_a_head = self
+                .players
+                .iter()
+                .filter(|p| p.is_alive())
+                .any(|p| p.get_snake_head().distance(&point, size) < OBSTACLE_DISTANCE);
+
+            if near_a_head || self.is_taken(&point) {
+                continue;
+            }
+
+            self.obstacles.push(point);
+            self.open_cells -= 1;
+            return;
+        }
+    }
+
+    /// Gives every kind of extra food its chance of turning up on this walk,
+    /// as set by the board. Boards without extra food never touch the random
+    /// generator here, so their games stay the same as before.
+    fn spawn_items(&mut self) {
+        let rates = *self.board.get_food_rates();
+
+        for kind in FoodKind::EXTRAS {
+            let rate = rates.get(kind);
+            if rate <= 0.0 || self.items.len() >= MAX_EXTRA_FOOD {
+                continue;
+            }
+            if !self.rng.gen_bool(rate.min(1.0)) {
+                continue;
+            }
+
+            match self.find_free_cell() {
+                Some(point) => self.items.push(Food::new(point, kind)),
+                None => return,
+            }
+        }
+    }
+
+    /// Turns up a random power-up now and then, as often as the board says.
+    fn spawn_power_ups(&mut self) {
+        let rate = self.board.get_power_up_rate();
+        if rate <= 0.0 || self.power_ups.len() >= MAX_POWER_UPS || !self.rng.gen_bool(rate.min(1.0))
+        {
+            return;
+        }
+
+        let kind = PowerUpKind::ALL[self.rng.gen_range(0..PowerUpKind::ALL.len())];
+        if let Some(point) = self.find_free_cell() {
+            self.power_ups.push(PowerUp::new(point, kind));
+        }
+    }
+
+    /// Moves the food a cell towards the nearest head of a snake with a
+    /// magnet, unless something is in the way.
+    fn attract_food(&mut self) {
+        let size = self.board.get_size();
+
+        let Some((dx, dy)) = self
+            .players
+            .iter()
+            .filter(|p| p.is_alive() && p.has_effect(PowerUpKind::Magnet))
+            .map(|p| self.food.offset(p.get_snake_head(), size))
+            .min_by_key(|(dx, dy)| dx.abs() + dy.abs())
+        else {
+            return;
+        };
+
+        let direction = match (dx, dy) {
+            (0, 0) => return,
+            (dx, dy) if dx.abs() >= dy.abs() && dx > 0 => Direction::Down,
+            (dx, dy) if dx.abs() >= dy.abs() => Direction::Up,
+            (_, dy) if dy > 0 => Direction::Right,
+            _ => Direction::Left,
+        };
+        let next = self.food.get_neighbor(&direction, size);
+        if !self.is_taken(&next) {
+            self.food = next;
+        }
+    }
+
+    /// Moves every entity of the board in turn. One moving into a snake's
+    /// head kills the snake, except in zen where it waits instead.
+    fn move_entities(&mut self) {
+        for index in 0..self.movers.len() {
+            let moved = self.moved(&self.movers[index]);
+
+            if moved.point != self.movers[index].point {
+                if let Some(player) = self
+                    .players
+                    .iter_mut()
+                    .find(|p| p.is_alive() && p.get_snake_head() == &moved.point)
+                {
+                    player.death = Some(Death::new(DeathCause::Enemy, moved.point.clone()));
+                }
+            }
+            self.movers[index] = moved;
+        }
+    }
+
+    /// `mover` after its next move: on along its patrol, straight on or
+    /// back for a bouncer, and a cell closer to the nearest head for a
+    /// chaser. It waits when the way is blocked.
+    fn moved(&self, mover: &Mover) -> Mover {
+        let size = self.board.get_size();
+        let mut moved = mover.clone();
+
+        match mover.get_entity().get_motion() {
+            Motion::Patrol(path) => {
+                if let Some((step, back)) = mover.next_step() {
+                    if self.lets_through(&path[step]) {
+                        moved.point = path[step].clone();
+                        moved.step = step;
+                        moved.back = back;
+                    }
+                }
+            }
+            Motion::Bounce(direction) => {
+                let ahead = mover.point.get_neighbor(direction, size);
+                if self.lets_through(&ahead) {
+                    moved.point = ahead;
+                } else {
+                    moved.turn_around();
+                    let behind = mover.point.get_neighbor(&direction.opposite(), size);
+                    if self.lets_through(&behind) {
+                        moved.point = behind;
+                    }
+                }
+            }
+            Motion::Chase => {
+                let Some(distance) = self
+                    .players
+                    .iter()
+                    .filter(|p| p.is_alive())
+                    .map(|p| p.get_snake_head())
+                    .min_by_key(|head| mover.point.distance(head, size))
+                    .map(|head| |point: &Point| point.distance(head, size))
+                else {
+                    return moved;
+                };
+
+                if let Some(next) = DIRECTIONS
+                    .iter()
+                    .map(|direction| mover.point.get_neighbor(direction, size))
+                    .filter(|point| self.lets_through(point))
+                    .filter(|point| distance(point) < distance(&mover.point))
+                    .min_by_key(|point| distance(point))
+                {
+                    moved.point = next;
+                }
+            }
+        }
+
+        moved
+    }
+
+    /// Whether an entity may move into `point`: nothing but a snake's head
+    /// is there, and not even that in zen.
+    fn lets_through(&self, point: &Point) -> bool {
+        let zen = self.mode.get_collision_penalty().is_some();
+        let on_snake = self.players.iter().any(|player| {
+            let head = player.is_alive() && !zen && player.get_snake_head() == point;
+            !head && Self::is_snake(player.get_snake(), point)
+        });
+
+        !self.is_wall(point)
+            && !matches!(self.board.get_tile(point), Tile::Portal(_))
+            && !self.movers.iter().any(|mover| mover.get_point() == point)
+            && !on_snake
+    }
+
+    /// Whether `point` holds a wall, a portal, a snake, an entity, any food
+    /// or a power-up.
+    fn is_taken(&self, point: &Point) -> bool {
+        self.is_wall(point)
+            || matches!(self.board.get_tile(point), Tile::Portal(_))
+            || self.movers.iter().any(|mover| mover.get_point() == point)
+            || *point == self.food
+            || self.items.iter().any(|item| item.get_point() == point)
+            || self.power_ups.iter().any(|p| p.get_point() == point)
+            || self
+                .players
+                .iter()
+                .any(|p| Self::is_snake(p.get_snake(), point))
+    }
+}
+
+impl Game<'_> {
+    fn put_snake(&self, result: &mut [Vec<String>], snake: &Snake) {
         fn get_char(before: &Direction, after: &Direction) -> String {
             match (before, after) {
                 (&Direction::Down, &Direction::Right) | (&Direction::Right, &Direction::Down) => {
@@ -107,9 +853,7 @@ impl Game<'_> {
                 | (&Direction::Up, &Direction::None)
                 | (&Direction::Up, &Direction::Up)
                 | (&Direction::Down, &Direction::Down) => " │".to_string(),
-                _ => {
-                    panic!("{:?} {:?}", before, after)
-                }
+                (&Direction::None, &Direction::None) => " ■".to_string(),
             }
         }
 
@@ -119,11 +863,11 @@ impl Game<'_> {
             let mut current = first;
 
             for next in iter {
-                let char_to_set = get_char(&prev_direction, &current.direction_of_neighbor(next));
+                let char_to_set = get_char(&prev_direction, &self.link(current, next));
                 result[(current.get_x() + 1) as usize][(current.get_y() + 1) as usize] =
                     char_to_set;
 
-                prev_direction = next.direction_of_neighbor(current);
+                prev_direction = self.link(next, current);
                 current = next;
             }
 
@@ -132,40 +876,119 @@ impl Game<'_> {
         }
     }
 
+    /// The way from the segment `from` to the segment `to` of a snake: to
+    /// a neighbor, or into the portal next to `from` that comes out next to
+    /// `to`.
+    fn link(&self, from: &Point, to: &Point) -> Direction {
+        let size = self.board.get_size();
+        let next_to = |point: &Point, other: &Point| {
+            DIRECTIONS
+                .iter()
+                .any(|direction| point.get_neighbor(direction, size) == *other)
+        };
+
+        DIRECTIONS
+            .into_iter()
+            .find(|direction| from.get_neighbor(direction, size) == *to)
+            .or_else(|| {
+                DIRECTIONS.into_iter().find(|direction| {
+                    self.board
+                        .get_partner(&from.get_neighbor(direction, size))
+                        .is_some_and(|partner| next_to(partner, to))
+                })
+            })
+            .unwrap_or(Direction::None)
+    }
+
     fn put_food(result: &mut [Vec<String>], food: &Point) {
         result[(food.get_x() + 1) as usize][(food.get_y() + 1) as usize] = " ●".to_string();
     }
+
+    /// Bonus food shows the tens of walks it has left as a countdown.
+    fn put_item(result: &mut [Vec<String>], item: &Food) {
+        let point = item.get_point();
+        let glyph = match item.get_kind() {
+            FoodKind::Normal => " ●".to_string(),
+            FoodKind::Bonus => format!("{:>2}", item.get_ticks_left().unwrap_or(0).div_ceil(10)),
+            FoodKind::Poison => " ×".to_string(),
+            FoodKind::Golden => " ◆".to_string(),
+        };
+        result[(point.get_x() + 1) as usize][(point.get_y() + 1) as usize] = glyph;
+    }
 }
 
 impl Game<'_> {
+    #[cfg(test)]
     fn create_snake(table_size: u16, length: u16) -> Snake {
         let half = (table_size as i16 - 1) / 2;
+
+        Self::spawn_snake(
+            &Spawn::new(Point::new(half, half), Direction::Right),
+            table_size,
+            length,
+        )
+    }
+
+    /// A straight snake centered on the spawn point with its head towards the
+    /// spawn direction.
+    fn spawn_snake(spawn: &Spawn, table_size: u16, length: u16) -> Snake {
         let offset = length as i16 / 2;
 
-        let range = if length % 2 == 0 {
+        let range = if length.is_multiple_of(2) {
             -offset..=offset - 1
         } else {
             -offset..=offset
         };
 
-        range.rev().map(|i| Point::new(half, half + i)).collect()
+        range
+            .rev()
+            .map(|i| {
+                spawn
+                    .get_point()
+                    .shifted(&spawn.get_direction(), i, table_size)
+            })
+            .collect()
     }
 
-    fn find_lunch_point(snake: &Snake, board: &Board) -> Food {
-        let table_size = board.get_size();
-        let mut rng = rand::thread_rng();
-        let mut food = Self::get_head(snake);
-        while Self::is_snake(snake, &food) || board.is_wall(&food) {
-            food = Food::new(
-                rng.gen_range(0..table_size as i16),
-                rng.gen_range(0..table_size as i16),
-            );
+    /// A random cell without a wall, a snake, food or an entity, or `None`
+    /// once there is none left.
+    fn find_free_cell(&mut self) -> Option<Point> {
+        let snakes: usize = self.players.iter().map(|p| p.get_snake().len()).sum();
+        let food = !self
+            .players
+            .iter()
+            .any(|p| Self::is_snake(p.get_snake(), &self.food));
+        // entities only count on open cells nothing else already takes
+        let movers = self
+            .movers
+            .iter()
+            .map(|mover| mover.get_point())
+            .filter(|point| {
+                !self.is_wall(point)
+                    && !matches!(self.board.get_tile(point), Tile::Portal(_))
+                    && **point != self.food
+                    && !self.items.iter().any(|item| item.get_point() == *point)
+                    && !self.power_ups.iter().any(|p| p.get_point() == *point)
+                    && !self
+                        .players
+                        .iter()
+                        .any(|p| Self::is_snake(p.get_snake(), point))
+            })
+            .count();
+        let taken = snakes + self.items.len() + self.power_ups.len() + food as usize + movers;
+        if taken >= self.open_cells {
+            return None;
         }
-        food
-    }
 
-    fn get_head(snake: &Snake) -> Point {
-        (*snake.front().unwrap()).clone()
+        let table_size = self.board.get_size();
+        let mut cell = self.players[0].get_snake_head().clone();
+        while self.is_taken(&cell) {
+            cell = Point::new(
+                self.rng.gen_range(0..table_size as i16),
+                self.rng.gen_range(0..table_size as i16),
+            );
+        }
+        Some(cell)
     }
 
     fn is_snake(snake: &Snake, point: &Point) -> bool {
@@ -177,7 +1000,10 @@ impl Game<'_> {
 mod test_game {
     use std::collections::LinkedList;
 
-    use crate::core::{point::Point, Board, Direction, Game};
+    use crate::core::{
+        food::FoodRates, point::Point, power_up, Board, Death, DeathCause, Direction, Entity, Food,
+        FoodKind, Game, GameMode, Mover, PowerUp, PowerUpKind, Tile, Wall,
+    };
 
     #[test]
     fn check_create_table() {
@@ -223,19 +1049,19 @@ mod test_game {
         game.food = Point::new(0, 0);
 
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([Point::new(2, 3), Point::new(2, 2), Point::new(2, 1)])
         );
         assert!(game.walk());
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([Point::new(2, 4), Point::new(2, 3), Point::new(2, 2)])
         );
         game.rotation(Direction::Down);
 
         assert!(game.walk());
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([Point::new(3, 4), Point::new(2, 4), Point::new(2, 3)])
         );
 
@@ -243,7 +1069,7 @@ mod test_game {
 
         assert!(game.walk());
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([Point::new(3, 3), Point::new(3, 4), Point::new(2, 4)])
         );
 
@@ -251,7 +1077,7 @@ mod test_game {
 
         assert!(game.walk());
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([Point::new(2, 3), Point::new(3, 3), Point::new(3, 4)])
         );
 
@@ -259,16 +1085,32 @@ mod test_game {
         let mut game = Game::new(&board, 5);
         game.food = Point::new(6, 6);
 
-        game.snake = LinkedList::from([
+        game.players[0].snake = LinkedList::from([
             Point::new(1, 1),
             Point::new(2, 1),
             Point::new(2, 0),
             Point::new(1, 0),
             Point::new(0, 0),
         ]);
-        game.direction = Direction::Left;
+        game.players[0].direction = Direction::Left;
+
+        assert!(!game.walk());
+        assert_eq!(
+            game.get_death(),
+            Some(&Death::new(DeathCause::Body, Point::new(1, 0)))
+        );
+        assert_eq!(game.get_snake_head(), &Point::new(1, 1));
+
+        let board = Board::new("test".to_string(), 7, Vec::from([Wall::new(3, 6)]));
+        let mut game = Game::new(&board, 3);
+        game.food = Point::new(0, 0);
 
+        assert!(game.walk());
         assert!(!game.walk());
+        assert_eq!(
+            game.get_death(),
+            Some(&Death::new(DeathCause::Wall, Point::new(3, 6)))
+        );
     }
 
     #[test]
@@ -277,7 +1119,7 @@ mod test_game {
         let mut game = Game::new(&board, 3);
 
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([Point::new(3, 4), Point::new(3, 3), Point::new(3, 2)])
         );
 
@@ -286,17 +1128,17 @@ mod test_game {
         assert!(game.walk());
 
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([Point::new(3, 5), Point::new(3, 4), Point::new(3, 3)])
         );
 
         game.rotation(Direction::Down);
 
-        assert_eq!(game.direction, Direction::Down);
+        assert_eq!(game.players[0].direction, Direction::Down);
 
         assert!(game.walk());
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([
                 Point::new(4, 5),
                 Point::new(3, 5),
@@ -317,7 +1159,7 @@ mod test_game {
 
         assert!(game.walk());
         assert_eq!(
-            game.snake,
+            game.players[0].snake,
             LinkedList::from([
                 Point::new(5, 5),
                 Point::new(4, 5),
@@ -328,4 +1170,566 @@ mod test_game {
 
         assert!(game.walk());
     }
+
+    #[test]
+    fn rotation_between_walks() {
+        let board = Board::new("test".to_string(), 7, Vec::new());
+        let mut game = Game::new(&board, 3);
+
+        game.rotation(Direction::Up);
+        game.rotation(Direction::Left);
+        assert_eq!(game.players[0].direction, Direction::Up);
+
+        assert!(game.walk());
+        game.rotation(Direction::Left);
+        assert_eq!(game.players[0].direction, Direction::Left);
+    }
+
+    #[test]
+    fn same_seed_same_food() {
+        let board = Board::new("test".to_string(), 20, Vec::new());
+
+        let game = Game::with_seed(&board, 3, 42);
+        let restarted = Game::with_seed(&board, 3, 7).restart(42);
+
+        assert_eq!(game.food, restarted.food);
+        assert_eq!(restarted.get_seed(), 42);
+    }
+
+    #[test]
+    fn multiplayer_collisions() {
+        let board = Board::new("test".to_string(), 9, Vec::new());
+        let game = Game::with_players(&board, 3, 1, 2);
+
+        assert_eq!(game.players[0].get_snake_head(), &Point::new(3, 5));
+        assert_eq!(game.players[1].get_snake_head(), &Point::new(6, 3));
+
+        let mut game = Game::with_players(&board, 3, 1, 2);
+        game.food = Point::new(0, 0);
+        game.players[0].snake =
+            LinkedList::from([Point::new(4, 2), Point::new(4, 1), Point::new(4, 0)]);
+        game.players[1].snake =
+            LinkedList::from([Point::new(4, 4), Point::new(4, 5), Point::new(4, 6)]);
+
+        assert!(!game.walk());
+        for player in &game.players {
+            assert_eq!(
+                player.get_death(),
+                Some(&Death::new(DeathCause::HeadOn, Point::new(4, 3)))
+            );
+        }
+        assert_eq!(game.get_winner(), None);
+
+        let mut game = Game::with_players(&board, 3, 1, 2);
+        game.food = Point::new(0, 0);
+        game.players[1].snake =
+            LinkedList::from([Point::new(4, 4), Point::new(5, 4), Point::new(6, 4)]);
+        game.rotate(1, Direction::Up);
+
+        assert!(!game.walk());
+        assert_eq!(
+            game.players[1].get_death(),
+            Some(&Death::new(DeathCause::Snake, Point::new(3, 4)))
+        );
+        assert_eq!(game.get_snake_head(), &Point::new(3, 6));
+        assert_eq!(game.get_winner(), Some(0));
+
+        let mut game = Game::with_players(&board, 3, 1, 3);
+        game.food = Point::new(0, 0);
+        game.players[0].snake =
+            LinkedList::from([Point::new(3, 5), Point::new(3, 4), Point::new(3, 3)]);
+        game.players[0].direction = Direction::Right;
+        game.players[0].heading = Direction::Right;
+        game.players[1].snake =
+            LinkedList::from([Point::new(3, 6), Point::new(4, 6), Point::new(5, 6)]);
+        game.forfeit(1);
+
+        game.walk();
+        assert_eq!(
+            game.players[0].get_death(),
+            Some(&Death::new(DeathCause::Snake, Point::new(3, 6)))
+        );
+    }
+
+    #[test]
+    fn clearing_the_board_ends_the_game() {
+        let board = Board::new("test".to_string(), 2, Vec::new());
+        let mut game = Game::with_seed(&board, 2, 3);
+        game.players[0].snake = LinkedList::from([Point::new(0, 1), Point::new(0, 0)]);
+        game.players[0].direction = Direction::Down;
+        game.food = Point::new(1, 1);
+
+        assert!(game.walk());
+        assert!(!game.is_cleared());
+
+        game.food = Point::new(1, 0);
+        game.rotation(Direction::Left);
+        assert!(!game.walk());
+        assert!(game.is_cleared());
+        assert!(game.get_death().is_none());
+        assert_eq!(game.get_score(), 2);
+    }
+
+    #[test]
+    fn time_attack_ends_on_time() {
+        let board = Board::new("test".to_string(), 20, Vec::new());
+        let mut game = Game::with_mode(&board, 3, 1, 1, GameMode::TimeAttack);
+        game.food = Point::new(0, 0);
+
+        while game.walk() {}
+
+        assert_eq!(game.get_ticks(), 600);
+        assert_eq!(game.get_ticks_left(), Some(0));
+        assert!(game.get_death().is_none());
+        assert_eq!(game.get_replay().get_mode(), GameMode::TimeAttack);
+    }
+
+    #[test]
+    fn survival_adds_obstacles() {
+        let board = Board::new("test".to_string(), 20, Vec::new());
+        let mut game = Game::with_mode(&board, 3, 4, 1, GameMode::Survival);
+        game.food = Point::new(0, 0);
+
+        for _ in 0..50 {
+            assert!(game.walk());
+        }
+
+        assert_eq!(game.get_obstacles().len(), 2);
+        let table = game.get_table();
+        for obstacle in game.get_obstacles() {
+            assert!(game.is_wall(obstacle));
+            assert!(!game.get_players()[0].get_snake().contains(obstacle));
+            assert_eq!(
+                table[(obstacle.get_x() + 1) as usize][(obstacle.get_y() + 1) as usize],
+                "▓▓"
+            );
+        }
+
+        let mut replayed = game.get_replay().start(&board);
+        replayed.food = Point::new(0, 0);
+        for _ in 0..50 {
+            replayed.walk();
+        }
+        assert_eq!(replayed.get_obstacles(), game.get_obstacles());
+    }
+
+    #[test]
+    fn zen_collisions_cost_length() {
+        let board = Board::new("test".to_string(), 7, Vec::from([Wall::new(3, 6)]));
+        let mut game = Game::with_mode(&board, 3, 1, 1, GameMode::Zen);
+        game.food = Point::new(0, 0);
+
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(game.players[0].snake, LinkedList::from([Point::new(3, 5)]));
+        assert!(game.get_death().is_none());
+
+        // the snake did not move, so it still cannot turn back
+        game.rotation(Direction::Left);
+        assert_eq!(game.players[0].direction, Direction::Right);
+        game.rotation(Direction::Up);
+        assert!(game.walk());
+        assert_eq!(game.get_snake_head(), &Point::new(2, 5));
+    }
+
+    #[test]
+    fn resumes_where_it_was_saved() {
+        let board = Board::new("test".to_string(), 12, Vec::new());
+        let mut game = Game::with_mode(&board, 3, 9, 1, GameMode::Survival);
+        for _ in 0..30 {
+            game.walk();
+        }
+
+        let json = serde_json::to_string(&game.save()).unwrap();
+        let mut resumed = Game::resume(&board, serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(resumed.get_obstacles().len(), 1);
+
+        let turns = [Direction::Up, Direction::Left, Direction::Down];
+        for turn in turns.iter().cycle().take(60) {
+            game.rotation(*turn);
+            resumed.rotation(*turn);
+            assert_eq!(game.walk(), resumed.walk());
+            assert_eq!(game.snapshot(), resumed.snapshot());
+        }
+        assert_eq!(game.get_replay(), resumed.get_replay());
+        assert_eq!(game.get_obstacles(), resumed.get_obstacles());
+
+        let other = Board::new("other".to_string(), 12, Vec::new());
+        assert!(Game::resume(&other, game.save()).is_err());
+
+        let mut saved = game.save();
+        saved.obstacles.push(Point::new(12, 0));
+        assert!(Game::resume(&board, saved).is_err());
+        let mut saved = game.save();
+        saved.broken.push(Point::new(0, 0));
+        assert!(Game::resume(&board, saved).is_err());
+        let mut breakable = board.clone();
+        breakable
+            .set_tile(Point::new(0, 0), Tile::Breakable)
+            .unwrap();
+        let mut saved = game.save();
+        saved.broken.push(Point::new(0, 0));
+        assert!(Game::resume(&breakable, saved.clone()).is_ok());
+        saved.broken.push(Point::new(0, 0));
+        assert!(Game::resume(&breakable, saved).is_err());
+        let mut saved = game.save();
+        saved.obstacles = vec![Point::new(0, 0); 200];
+        assert!(Game::resume(&board, saved).is_err());
+    }
+
+    #[test]
+    fn every_kind_of_food_does_its_own_thing() {
+        let board = Board::new("test".to_string(), 20, Vec::new());
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(0, 0);
+        game.items = Vec::from([
+            Food::new(Point::new(9, 11), FoodKind::Bonus),
+            Food::new(Point::new(9, 12), FoodKind::Golden),
+            Food::new(Point::new(9, 16), FoodKind::Poison),
+        ]);
+
+        assert!(game.walk());
+        assert_eq!(game.get_score(), 3);
+        assert_eq!(game.players[0].snake.len(), 4);
+
+        assert!(game.walk());
+        assert_eq!(game.get_score(), 4);
+        for length in [5, 6, 7] {
+            assert_eq!(game.players[0].snake.len(), length);
+            assert!(game.walk());
+        }
+        assert_eq!(game.players[0].snake.len(), 7);
+
+        assert!(game.walk());
+        assert_eq!(game.get_snake_head(), &Point::new(9, 16));
+        assert_eq!(game.players[0].snake.len(), 5);
+        assert_eq!(game.get_score(), 4);
+        assert!(game.get_items().is_empty());
+    }
+
+    #[test]
+    fn bonus_food_counts_down_and_goes_away() {
+        let board = Board::new("test".to_string(), 20, Vec::new());
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(0, 0);
+        game.items = Vec::from([Food::new(Point::new(0, 1), FoodKind::Bonus)]);
+
+        assert_eq!(game.get_table()[1][2], " 4");
+        for _ in 0..39 {
+            assert!(game.walk());
+        }
+        assert_eq!(game.get_items()[0].get_ticks_left(), Some(1));
+        assert_eq!(game.get_table()[1][2], " 1");
+
+        assert!(game.walk());
+        assert!(game.get_items().is_empty());
+    }
+
+    #[test]
+    fn boards_set_how_often_extra_food_shows_up() {
+        let mut board = Board::new("test".to_string(), 20, Vec::new());
+        board.set_food_rates(FoodRates {
+            bonus: 1.0,
+            poison: 1.0,
+            golden: 1.0,
+        });
+        let mut game = Game::with_seed(&board, 3, 5);
+
+        assert!(game.walk());
+        let kinds: Vec<FoodKind> = game.get_items().iter().map(|i| i.get_kind()).collect();
+        assert_eq!(kinds, FoodKind::EXTRAS);
+        for item in game.get_items() {
+            assert_ne!(item.get_point(), game.get_food());
+            assert!(!game.players[0].snake.contains(item.get_point()));
+        }
+
+        let mut replayed = game.get_replay().start(&board);
+        replayed.walk();
+        assert_eq!(replayed.snapshot(), game.snapshot());
+    }
+
+    #[test]
+    fn speed_power_ups_work_for_each_snake() {
+        let board = Board::new("test".to_string(), 20, Vec::new());
+        let mut game = Game::with_players(&board, 3, 1, 2);
+        game.food = Point::new(0, 0);
+        power_up::stack(&mut game.players[0].effects, PowerUpKind::SpeedBoost);
+        power_up::stack(&mut game.players[1].effects, PowerUpKind::SlowMotion);
+        let heads: Vec<Point> = game
+            .players
+            .iter()
+            .map(|player| player.get_snake_head().clone())
+            .collect();
+
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(heads[0].distance(game.players[0].get_snake_head(), 20), 4);
+        assert_eq!(heads[1].distance(game.players[1].get_snake_head(), 20), 1);
+        assert_eq!(game.players[0].get_effects()[0].get_ticks_left(), 48);
+    }
+
+    #[test]
+    fn power_ups_bend_the_rules_for_a_while() {
+        let board = Board::new("test".to_string(), 20, Vec::from([Wall::new(9, 13)]));
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(9, 12);
+        game.power_ups = Vec::from([
+            PowerUp::new(Point::new(9, 11), PowerUpKind::WallPhasing),
+            PowerUp::new(Point::new(10, 13), PowerUpKind::ScoreMultiplier),
+        ]);
+
+        assert!(game.walk());
+        assert!(game.players[0].has_effect(PowerUpKind::WallPhasing));
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(game.get_snake_head(), &Point::new(9, 13));
+        assert_eq!(game.players[0].get_effects()[0].get_ticks_left(), 28);
+
+        game.food = Point::new(11, 13);
+        game.rotation(Direction::Down);
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(game.get_score(), 1 + 2);
+        assert!(game.get_power_ups().is_empty());
+
+        let mut game = Game::with_seed(&board, 5, 2);
+        game.food = Point::new(0, 0);
+        game.power_ups = Vec::from([PowerUp::new(Point::new(9, 12), PowerUpKind::Ghost)]);
+        assert!(game.walk());
+        game.rotation(Direction::Down);
+        assert!(game.walk());
+        game.rotation(Direction::Left);
+        assert!(game.walk());
+        game.rotation(Direction::Up);
+        assert!(game.walk());
+        assert_eq!(game.get_snake_head(), &Point::new(9, 11));
+        assert!(game.get_death().is_none());
+
+        for _ in 0..37 {
+            assert!(game.players[0].has_effect(PowerUpKind::Ghost));
+            game.walk();
+        }
+        assert!(game.players[0].get_effects().is_empty());
+    }
+
+    #[test]
+    fn magnets_pull_the_food_and_power_ups_stack() {
+        let board = Board::new("test".to_string(), 20, Vec::new());
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(9, 15);
+        game.power_ups = Vec::from([
+            PowerUp::new(Point::new(9, 11), PowerUpKind::Magnet),
+            PowerUp::new(Point::new(9, 12), PowerUpKind::SpeedBoost),
+            PowerUp::new(Point::new(3, 3), PowerUpKind::SpeedBoost),
+        ]);
+
+        assert!(game.walk());
+        assert_eq!(game.get_food(), &Point::new(9, 14));
+        assert!(!game.players[0].has_effect(PowerUpKind::SpeedBoost));
+        assert!(game.walk());
+        assert_eq!(game.get_food(), &Point::new(9, 13));
+        assert!(game.players[0].has_effect(PowerUpKind::SpeedBoost));
+        assert!(game.walk());
+        assert_eq!(game.get_score(), 1);
+        assert_eq!(game.get_snake_head(), &Point::new(9, 14));
+
+        game.power_ups[0] = PowerUp::new(Point::new(9, 15), PowerUpKind::SpeedBoost);
+        assert!(game.walk());
+        let speed = &game.players[0].get_effects()[1];
+        assert_eq!(speed.get_kind(), PowerUpKind::SpeedBoost);
+        assert_eq!(speed.get_ticks_left(), 48 + 50);
+    }
+
+    #[test]
+    fn power_ups_turn_up_the_same_in_replays() {
+        let mut board = Board::new("test".to_string(), 20, Vec::new());
+        board.set_power_up_rate(1.0);
+        let mut game = Game::with_seed(&board, 3, 7);
+
+        assert!(game.walk());
+        assert_eq!(game.get_power_ups().len(), 1);
+        assert!(game.walk());
+        assert_eq!(game.get_power_ups().len(), 2);
+        assert!(game.walk());
+        assert_eq!(game.get_power_ups().len(), 2);
+
+        let mut replayed = game.get_replay().start(&board);
+        for _ in 0..3 {
+            replayed.walk();
+        }
+        assert_eq!(replayed.snapshot(), game.snapshot());
+    }
+
+    #[test]
+    fn tiles_change_how_snakes_move() {
+        let mut board = Board::new("test".to_string(), 20, Vec::new());
+        board
+            .set_tile(Point::new(9, 11), Tile::OneWay(Direction::Left))
+            .unwrap();
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(0, 0);
+        assert!(!game.walk());
+        assert_eq!(
+            game.get_death(),
+            Some(&Death::new(DeathCause::Wall, Point::new(9, 11)))
+        );
+
+        let tiles = [
+            Tile::OneWay(Direction::Right),
+            Tile::Mud,
+            Tile::Ice,
+            Tile::SpeedPad,
+        ];
+        for (y, tile) in (11..).zip(tiles) {
+            board.set_tile(Point::new(9, y), tile).unwrap();
+        }
+        board.set_tile(Point::new(9, 16), Tile::Breakable).unwrap();
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(0, 0);
+
+        assert!(game.walk());
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(game.get_snake_head(), &Point::new(9, 12));
+        assert!(game.walk());
+        assert_eq!(game.get_snake_head(), &Point::new(9, 13));
+
+        game.rotation(Direction::Down);
+        assert_eq!(game.players[0].direction, Direction::Right);
+        assert!(game.walk());
+        assert!(game.players[0].has_effect(PowerUpKind::SpeedBoost));
+
+        // the boost takes the snake onto the breakable wall in the same walk
+        assert!(game.is_wall(&Point::new(9, 16)));
+        assert!(game.walk());
+        assert_eq!(game.get_snake_head(), &Point::new(9, 15));
+        assert!(!game.is_wall(&Point::new(9, 16)));
+        assert_eq!(game.get_table()[10][17], "  ");
+        assert!(game.walk());
+        assert_eq!(game.get_snake_head(), &Point::new(9, 17));
+        assert!(game.get_death().is_none());
+
+        let mut replayed = game.get_replay().start(&board);
+        replayed.food = Point::new(0, 0);
+        for _ in 0..7 {
+            replayed.walk();
+        }
+        assert_eq!(replayed.snapshot(), game.snapshot());
+    }
+
+    #[test]
+    fn portals_carry_the_snake_across() {
+        let mut board = Board::new("test".to_string(), 20, Vec::new());
+        board.set_tile(Point::new(9, 12), Tile::Portal(0)).unwrap();
+        board.set_tile(Point::new(3, 5), Tile::Portal(0)).unwrap();
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(0, 0);
+
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(
+            game.players[0].snake,
+            LinkedList::from([Point::new(3, 6), Point::new(9, 11), Point::new(9, 10)])
+        );
+
+        let table = game.get_table();
+        assert_eq!(table[4][7], "──");
+        assert_eq!(table[10][12], "──");
+        assert_eq!(table[4][6], "@1");
+        assert_eq!(table[10][13], "@1");
+
+        game.rotation(Direction::Down);
+        assert!(game.walk());
+        let table = game.get_table();
+        assert_eq!(table[5][7], " │");
+        assert_eq!(table[4][7], "─┐");
+        assert_eq!(table[10][12], "──");
+
+        game.players[0].snake = LinkedList::from([Point::new(4, 6)]);
+        assert_eq!(game.get_table()[5][7], " ■");
+    }
+
+    #[test]
+    fn entities_move_every_other_walk() {
+        let mut board = Board::new("test".to_string(), 20, Vec::from([Wall::new(5, 0)]));
+        let path = Vec::from([Point::new(2, 2), Point::new(2, 3), Point::new(2, 4)]);
+        board.add_entity(Entity::patrol(path).unwrap());
+        board.add_entity(Entity::bounce(Point::new(5, 2), Direction::Left));
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(0, 0);
+
+        let points = |game: &Game| -> Vec<Point> {
+            game.get_movers()
+                .iter()
+                .map(|mover| mover.get_point().clone())
+                .collect()
+        };
+        assert!(game.walk());
+        assert_eq!(points(&game), [Point::new(2, 2), Point::new(5, 2)]);
+        assert!(game.walk());
+        assert_eq!(points(&game), [Point::new(2, 3), Point::new(5, 1)]);
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(points(&game), [Point::new(2, 4), Point::new(5, 2)]);
+        assert!(game.walk());
+        assert!(game.walk());
+        assert_eq!(points(&game), [Point::new(2, 3), Point::new(5, 3)]);
+        assert_eq!(game.get_table()[3][4], "◘◘");
+
+        let mut replayed = game.get_replay().start(&board);
+        replayed.food = Point::new(0, 0);
+        for _ in 0..6 {
+            replayed.walk();
+        }
+        assert_eq!(replayed.snapshot(), game.snapshot());
+    }
+
+    #[test]
+    fn entities_can_take_the_last_free_cells() {
+        let board = Board::new("test".to_string(), 5, Vec::new());
+        let mut game = Game::with_seed(&board, 3, 2);
+
+        let free: Vec<Point> = (0..5)
+            .flat_map(|x| (0..5).map(move |y| Point::new(x, y)))
+            .filter(|point| !game.is_taken(point))
+            .collect();
+        game.movers = free
+            .iter()
+            .skip(1)
+            .map(|point| Mover::new(&Entity::chase(point.clone())))
+            .collect();
+        assert_eq!(game.find_free_cell(), Some(free[0].clone()));
+
+        game.movers
+            .push(Mover::new(&Entity::chase(free[0].clone())));
+        assert_eq!(game.find_free_cell(), None);
+    }
+
+    #[test]
+    fn entities_kill_the_snake() {
+        let mut board = Board::new("test".to_string(), 20, Vec::new());
+        board.add_entity(Entity::chase(Point::new(9, 13)));
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(0, 0);
+
+        assert!(game.walk());
+        assert!(!game.walk());
+        assert_eq!(
+            game.get_death(),
+            Some(&Death::new(DeathCause::Enemy, Point::new(9, 12)))
+        );
+
+        let walls = Vec::from([Wall::new(8, 12), Wall::new(10, 12)]);
+        let mut board = Board::new("test".to_string(), 20, walls);
+        board.add_entity(Entity::bounce(Point::new(9, 12), Direction::Up));
+        let mut game = Game::with_seed(&board, 3, 2);
+        game.food = Point::new(0, 0);
+
+        assert!(game.walk());
+        assert!(!game.walk());
+        assert_eq!(
+            game.get_death(),
+            Some(&Death::new(DeathCause::Enemy, Point::new(9, 12)))
+        );
+    }
 }
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// Walks a time attack lasts, about a minute at the default speed.
+const TIME_ATTACK_TICKS: u32 = 600;
+/// Walks between two obstacles of a survival game.
+const SURVIVAL_INTERVAL: u32 = 25;
+/// Cells a zen snake loses when it runs into something.
+const ZEN_PENALTY: usize = 2;
+
+/// What a game asks of the players and how it is scored.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum GameMode {
+    /// Eat as much as possible before running into something.
+    #[default]
+    Classic,
+    /// Eat as much as possible in a fixed number of walks.
+    TimeAttack,
+    /// Live as long as possible while obstacles keep appearing; the score
+    /// is the walks survived.
+    Survival,
+    /// Nobody dies: running into something costs length instead.
+    Zen,
+}
+
+impl GameMode {
+    pub fn next(self) -> Self {
+        match self {
+            GameMode::Classic => GameMode::TimeAttack,
+            GameMode::TimeAttack => GameMode::Survival,
+            GameMode::Survival => GameMode::Zen,
+            GameMode::Zen => GameMode::Classic,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "classic",
+            GameMode::TimeAttack => "time attack",
+            GameMode::Survival => "survival",
+            GameMode::Zen => "zen",
+        }
+    }
+
+    /// The scoreboard the scores of this mode on `board_name` go to. Classic
+    /// keeps the plain board name, so the scores from before modes stay.
+    pub fn category(&self, board_name: &str) -> String {
+        match self {
+            GameMode::Classic => board_name.to_string(),
+            mode => format!("{} ({})", board_name, mode.get_name()),
+        }
+    }
+
+    /// The walks after which the game ends, if it has a limit.
+    pub fn get_time_limit(&self) -> Option<u32> {
+        match self {
+            GameMode::TimeAttack => Some(TIME_ATTACK_TICKS),
+            _ => None,
+        }
+    }
+
+    /// The walks between two new obstacles, if the board grows any.
+    pub fn get_obstacle_interval(&self) -> Option<u32> {
+        match self {
+            GameMode::Survival => Some(SURVIVAL_INTERVAL),
+            _ => None,
+        }
+    }
+
+    /// The cells a collision costs instead of the snake's life, if it does.
+    pub fn get_collision_penalty(&self) -> Option<usize> {
+        match self {
+            GameMode::Zen => Some(ZEN_PENALTY),
+            _ => None,
+        }
+    }
+
+    /// What a game with `score` food eaten over `ticks` walks puts on the
+    /// scoreboard.
+    pub fn points(&self, score: u16, ticks: u32) -> u16 {
+        match self {
+            GameMode::Survival => ticks.min(u16::MAX as u32) as u16,
+            _ => score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_mode {
+    use super::GameMode;
+
+    #[test]
+    fn every_mode_has_its_own_scoreboard() {
+        let mut mode = GameMode::default();
+        let mut categories = Vec::new();
+        for _ in 0..4 {
+            categories.push(mode.category("hard"));
+            mode = mode.next();
+        }
+
+        assert_eq!(mode, GameMode::Classic);
+        assert_eq!(categories[0], "hard");
+        assert_eq!(categories[1], "hard (time attack)");
+        categories.dedup();
+        assert_eq!(categories.len(), 4);
+
+        assert_eq!(GameMode::Survival.points(3, 120), 120);
+        assert_eq!(GameMode::Zen.points(3, 120), 3);
+    }
+}
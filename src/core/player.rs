@@ -0,0 +1,132 @@
+use std::collections::LinkedList;
+
+use super::{point::Point, Death, Direction, Effect, PowerUpKind};
+
+use serde::{Deserialize, Serialize};
+
+pub type Snake = LinkedList<Point>;
+
+/// One snake on the board together with its score.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Player {
+    pub(super) snake: Snake,
+    pub(super) direction: Direction,
+    pub(super) heading: Direction,
+    pub(super) score: u16,
+    pub(super) death: Option<Death>,
+    /// Cells the snake still grows by on its next walks.
+    #[serde(default)]
+    pub(super) growth: u16,
+    /// The power-ups working on the snake.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) effects: Vec<Effect>,
+    /// Whether the snake sits out its next walk, stuck in mud.
+    #[serde(default)]
+    pub(super) stalled: bool,
+}
+
+impl Player {
+    pub fn new(snake: Snake, direction: Direction) -> Self {
+        Self {
+            snake,
+            direction,
+            heading: direction,
+            score: 0,
+            death: None,
+            growth: 0,
+            effects: Vec::new(),
+            stalled: false,
+        }
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.score
+    }
+
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn get_snake(&self) -> &Snake {
+        &self.snake
+    }
+
+    pub fn get_snake_head(&self) -> &Point {
+        self.snake.front().unwrap()
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.death.is_none()
+    }
+
+    pub fn get_death(&self) -> Option<&Death> {
+        self.death.as_ref()
+    }
+
+    pub fn get_effects(&self) -> &[Effect] {
+        &self.effects
+    }
+
+    pub fn has_effect(&self, kind: PowerUpKind) -> bool {
+        self.effects.iter().any(|effect| effect.get_kind() == kind)
+    }
+
+    /// Whether a speed boost, not cancelled by slow motion, takes the snake
+    /// two cells a walk.
+    pub fn is_boosted(&self) -> bool {
+        self.has_effect(PowerUpKind::SpeedBoost) && !self.has_effect(PowerUpKind::SlowMotion)
+    }
+
+    /// Whether slow motion, not cancelled by a speed boost, holds the snake
+    /// back every other walk.
+    pub fn is_slowed(&self) -> bool {
+        self.has_effect(PowerUpKind::SlowMotion) && !self.has_effect(PowerUpKind::SpeedBoost)
+    }
+
+    /// What every food eaten is worth times its points.
+    pub fn get_multiplier(&self) -> u16 {
+        match self.has_effect(PowerUpKind::ScoreMultiplier) {
+            true => 2,
+            false => 1,
+        }
+    }
+
+    /// Turns before the next walk and returns whether the turn was taken.
+    ///
+    /// Turning back onto the neck is ignored, even after several turns
+    /// between two walks.
+    pub(super) fn rotation(&mut self, direction: Direction) -> bool {
+        if self.heading.is_opposite(&direction) || self.direction == direction {
+            return false;
+        }
+
+        self.direction = direction;
+        true
+    }
+
+    /// Moves the head into `head` and grows or shrinks the snake by `growth`
+    /// cells, keeping at least the head.
+    pub(super) fn advance(&mut self, head: Point, growth: i16) {
+        self.snake.push_front(head);
+        self.growth += growth.max(0) as u16;
+
+        match self.growth {
+            0 => {
+                self.snake.pop_back();
+            }
+            _ => self.growth -= 1,
+        }
+
+        if growth < 0 {
+            let length = self.snake.len().saturating_sub(-growth as usize).max(1);
+            self.snake.split_off(length);
+        }
+    }
+
+    /// The cell the head moves into on this walk.
+    pub(super) fn next_head(&mut self, table_size: u16) -> Point {
+        self.heading = self.direction;
+        self.get_snake_head()
+            .get_neighbor(&self.direction, table_size)
+    }
+}
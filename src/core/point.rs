@@ -38,6 +38,47 @@ impl Point {
         )
     }
 
+    /// The point `steps` cells away in `direction`, wrapping around the table.
+    pub fn shifted(&self, direction: &Direction, steps: i16, table_size: u16) -> Self {
+        let table_size_i16 = table_size as i16;
+
+        let (dx, dy) = match direction {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::None => (0, 0),
+        };
+
+        Point::new(
+            (self.x + dx * steps).rem_euclid(table_size_i16),
+            (self.y + dy * steps).rem_euclid(table_size_i16),
+        )
+    }
+
+    /// The steps from `self` to `other` along each axis, the shorter way
+    /// round the edges of the table, so neither is more than half of it.
+    pub fn offset(&self, other: &Self, table_size: u16) -> (i16, i16) {
+        let size = table_size as i16;
+        let along = |from: i16, to: i16| {
+            let delta = (to - from).rem_euclid(size);
+            match delta > size / 2 {
+                true => delta - size,
+                false => delta,
+            }
+        };
+
+        (along(self.x, other.x), along(self.y, other.y))
+    }
+
+    /// Steps between `self` and `other`, the edges of the table wrapping
+    /// around.
+    pub fn distance(&self, other: &Self, table_size: u16) -> i16 {
+        let (dx, dy) = self.offset(other, table_size);
+
+        dx.abs() + dy.abs()
+    }
+
     pub fn direction_of_neighbor(&self, other: &Self) -> Direction {
         match (other.x - self.x, other.y - self.y) {
             (1, 0) => Direction::Down,
@@ -82,4 +123,25 @@ mod test_point {
             Direction::None
         );
     }
+
+    #[test]
+    fn offset_wraps_around() {
+        let point = Point::new(1, 3);
+
+        assert_eq!(point.offset(&Point::new(2, 1), 5), (1, -2));
+        assert_eq!(point.offset(&Point::new(4, 0), 5), (-2, 2));
+        assert_eq!(point.offset(&Point::new(4, 3), 6), (3, 0));
+        assert_eq!(point.distance(&Point::new(4, 0), 5), 4);
+        assert_eq!(Point::new(4, 0).distance(&point, 5), 4);
+    }
+
+    #[test]
+    fn shifted() {
+        let point = Point::new(1, 3);
+
+        assert_eq!(point.shifted(&Direction::Right, 1, 5), Point { x: 1, y: 4 });
+        assert_eq!(point.shifted(&Direction::Right, 3, 5), Point { x: 1, y: 1 });
+        assert_eq!(point.shifted(&Direction::Up, 2, 5), Point { x: 4, y: 3 });
+        assert_eq!(point.shifted(&Direction::Left, -1, 5), Point { x: 1, y: 4 });
+    }
 }
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use super::point::Point;
+
+/// Walks a power-up lies on the board before it goes away.
+const LIFETIME: u32 = 80;
+
+/// What a power-up does to the snake that picks it up, for a while.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerUpKind {
+    /// The snake goes two cells a walk.
+    SpeedBoost,
+    /// The snake only goes on every other walk.
+    SlowMotion,
+    /// The snake passes through its own body.
+    Ghost,
+    /// The snake passes through walls and obstacles.
+    WallPhasing,
+    /// Food is worth twice as much.
+    ScoreMultiplier,
+    /// The food drifts towards the head.
+    Magnet,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 6] = [
+        PowerUpKind::SpeedBoost,
+        PowerUpKind::SlowMotion,
+        PowerUpKind::Ghost,
+        PowerUpKind::WallPhasing,
+        PowerUpKind::ScoreMultiplier,
+        PowerUpKind::Magnet,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PowerUpKind::SpeedBoost => "Speed",
+            PowerUpKind::SlowMotion => "Slow",
+            PowerUpKind::Ghost => "Ghost",
+            PowerUpKind::WallPhasing => "Phase",
+            PowerUpKind::ScoreMultiplier => "x2",
+            PowerUpKind::Magnet => "Magnet",
+        }
+    }
+
+    /// The letter the power-up shows on the board.
+    pub fn get_symbol(&self) -> char {
+        match self {
+            PowerUpKind::SpeedBoost => 'S',
+            PowerUpKind::SlowMotion => 'L',
+            PowerUpKind::Ghost => 'G',
+            PowerUpKind::WallPhasing => 'W',
+            PowerUpKind::ScoreMultiplier => 'X',
+            PowerUpKind::Magnet => 'M',
+        }
+    }
+
+    /// Walks the effect lasts once picked up.
+    pub fn get_duration(&self) -> u32 {
+        match self {
+            PowerUpKind::SpeedBoost | PowerUpKind::SlowMotion => 50,
+            PowerUpKind::Ghost | PowerUpKind::Magnet => 40,
+            PowerUpKind::WallPhasing => 30,
+            PowerUpKind::ScoreMultiplier => 60,
+        }
+    }
+}
+
+/// A power-up lying on the board, waiting to be picked up.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PowerUp {
+    point: Point,
+    kind: PowerUpKind,
+    ticks_left: u32,
+}
+
+impl PowerUp {
+    pub fn new(point: Point, kind: PowerUpKind) -> Self {
+        Self {
+            point,
+            kind,
+            ticks_left: LIFETIME,
+        }
+    }
+
+    pub fn get_point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn get_kind(&self) -> PowerUpKind {
+        self.kind
+    }
+
+    /// Counts one walk down and returns whether the power-up is still there.
+    pub(super) fn age(&mut self) -> bool {
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+        self.ticks_left > 0
+    }
+}
+
+/// A power-up a snake picked up, working for the walks it has left.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Effect {
+    kind: PowerUpKind,
+    ticks_left: u32,
+}
+
+impl Effect {
+    pub fn get_kind(&self) -> PowerUpKind {
+        self.kind
+    }
+
+    pub fn get_ticks_left(&self) -> u32 {
+        self.ticks_left
+    }
+}
+
+/// Adds `kind` to `effects`; picking up one that is already working adds
+/// its duration to what is left of it.
+pub(super) fn stack(effects: &mut Vec<Effect>, kind: PowerUpKind) {
+    match effects.iter_mut().find(|effect| effect.kind == kind) {
+        Some(effect) => effect.ticks_left += kind.get_duration(),
+        None => effects.push(Effect {
+            kind,
+            ticks_left: kind.get_duration(),
+        }),
+    }
+}
+
+/// Counts one walk down on every effect and drops the ones that ran out.
+pub(super) fn expire(effects: &mut Vec<Effect>) {
+    effects.retain_mut(|effect| {
+        effect.ticks_left = effect.ticks_left.saturating_sub(1);
+        effect.ticks_left > 0
+    });
+}
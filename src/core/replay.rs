@@ -0,0 +1,112 @@
+use super::{Board, Direction, Game, GameMode};
+
+use serde::{Deserialize, Serialize};
+
+/// Everything needed to play a game again move by move: the seed it started
+/// from and the turns every player made.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Replay {
+    seed: u64,
+    length: u16,
+    players: usize,
+    mode: GameMode,
+    turns: Vec<(u32, usize, Direction)>,
+    ticks: u32,
+}
+
+impl Replay {
+    pub fn new(seed: u64, length: u16, players: usize, mode: GameMode) -> Self {
+        Self {
+            seed,
+            length,
+            players,
+            mode,
+            turns: Vec::new(),
+            ticks: 0,
+        }
+    }
+
+    pub fn get_length(&self) -> u16 {
+        self.length
+    }
+
+    pub fn get_mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// Number of walks the recorded game made.
+    pub fn get_ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    pub fn record(&mut self, tick: u32, player: usize, direction: Direction) {
+        self.turns.push((tick, player, direction));
+    }
+
+    /// Counts one more walk of the recorded game.
+    pub fn advance(&mut self) {
+        self.ticks += 1;
+    }
+
+    /// A new game in the state the recorded one started from.
+    pub fn start<'a>(&self, board: &'a Board) -> Game<'a> {
+        Game::with_mode(board, self.length, self.seed, self.players, self.mode)
+    }
+
+    /// Turns the recorded game made right before its `tick`-th walk.
+    pub fn turns_at(&self, tick: u32) -> impl Iterator<Item = (usize, Direction)> + '_ {
+        self.turns
+            .iter()
+            .filter(move |(at, _, _)| *at == tick)
+            .map(|(_, player, direction)| (*player, *direction))
+    }
+
+    /// Plays the recorded turns on `game` for its next walk.
+    ///
+    /// Returns `false` once the game dies or the recording runs out.
+    pub fn step(&self, game: &mut Game) -> bool {
+        if game.get_ticks() >= self.ticks {
+            return false;
+        }
+
+        for (player, direction) in self.turns_at(game.get_ticks()) {
+            game.rotate(player, direction);
+        }
+
+        game.walk()
+    }
+}
+
+#[cfg(test)]
+mod test_replay {
+    use crate::core::{Board, Direction, Game};
+
+    #[test]
+    fn replays_the_same_game() {
+        let board = Board::new("test".to_string(), 9, Vec::new());
+        let mut game = Game::with_seed(&board, 3, 5);
+
+        let turns = [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ];
+        for tick in 0..40 {
+            if tick % 3 == 0 {
+                game.rotation(turns[(tick / 3) % turns.len()]);
+            }
+            if !game.walk() {
+                break;
+            }
+        }
+
+        let replay = game.get_replay().clone();
+        let mut replayed = replay.start(&board);
+        while replay.step(&mut replayed) {}
+
+        assert_eq!(replayed.get_table(), game.get_table());
+        assert_eq!(replayed.get_score(), game.get_score());
+        assert_eq!(replayed.get_replay(), &replay);
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::VecDeque;
+
+use super::Game;
+
+/// The last states of a game, newest last, to go back to them after a
+/// mistake in practice.
+pub struct Rewind<'a> {
+    states: VecDeque<Game<'a>>,
+    capacity: usize,
+}
+
+impl<'a> Rewind<'a> {
+    /// Keeps at most `capacity` states; the oldest goes once it is full.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            states: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Remembers `game` as it is right before a walk.
+    pub fn record(&mut self, game: &Game<'a>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.states.len() == self.capacity {
+            self.states.pop_front();
+        }
+
+        self.states.push_back(game.clone());
+    }
+
+    /// The game as it was before the last recorded walk, which is forgotten.
+    pub fn back(&mut self) -> Option<Game<'a>> {
+        self.states.pop_back()
+    }
+
+    /// How many walks can be taken back.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.states.clear();
+    }
+}
+
+#[cfg(test)]
+mod test_rewind {
+    use super::Rewind;
+    use crate::core::{Board, Direction, Game};
+
+    #[test]
+    fn goes_back_a_few_walks() {
+        let board = Board::new("test".to_string(), 9, Vec::new());
+        let mut game = Game::with_seed(&board, 3, 2);
+        let mut rewind = Rewind::new(3);
+
+        let mut states = Vec::new();
+        for turn in [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Left,
+        ] {
+            rewind.record(&game);
+            states.push(game.snapshot());
+            game.rotation(turn);
+            game.walk();
+        }
+        assert_eq!(rewind.len(), 3);
+
+        let back = rewind.back().unwrap();
+        assert_eq!(back.snapshot(), states[3]);
+        assert_eq!(back.get_ticks(), 3);
+        assert_eq!(rewind.back().unwrap().snapshot(), states[2]);
+        assert_eq!(rewind.back().unwrap().snapshot(), states[1]);
+        assert!(rewind.back().is_none());
+
+        // the game goes on from where it went back to as if nothing happened
+        let mut again = back.clone();
+        again.rotation(Direction::Left);
+        again.walk();
+        assert_eq!(again.snapshot(), game.snapshot());
+        assert_eq!(again.get_replay(), game.get_replay());
+    }
+}
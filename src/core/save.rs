@@ -0,0 +1,37 @@
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{point::Point, Food, GameMode, Mover, Player, PowerUp, Replay};
+
+/// A game stopped halfway, with everything needed to go on exactly where it
+/// was left: the snakes, the food, the random generator and the recording.
+///
+/// The board is kept by name, so the game goes on on the same board.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedGame {
+    pub(super) board: String,
+    pub(super) players: Vec<Player>,
+    pub(super) food: Point,
+    #[serde(default)]
+    pub(super) items: Vec<Food>,
+    #[serde(default)]
+    pub(super) power_ups: Vec<PowerUp>,
+    pub(super) length: u16,
+    pub(super) seed: u64,
+    pub(super) rng: ChaCha8Rng,
+    pub(super) ticks: u32,
+    pub(super) replay: Replay,
+    pub(super) cleared: bool,
+    pub(super) mode: GameMode,
+    pub(super) obstacles: Vec<Point>,
+    #[serde(default)]
+    pub(super) broken: Vec<Point>,
+    #[serde(default)]
+    pub(super) movers: Vec<Mover>,
+}
+
+impl SavedGame {
+    pub fn get_board_name(&self) -> &str {
+        &self.board
+    }
+}
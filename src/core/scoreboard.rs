@@ -4,9 +4,73 @@ use std::{
     io::{BufReader, Write},
 };
 
+use serde::{Deserialize, Serialize};
+
 const JSON_FILE_PATH: &str = "./src/scoreboard.json";
+const ANONYMOUS: &str = "anonymous";
+const HIGH_SCORES: usize = 10;
+
+type ScoreboardType = HashMap<String, Vec<Record>>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "StoredRecord")]
+pub struct Record {
+    name: String,
+    score: u16,
+}
+
+/// Scores used to be stored without a player name.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredRecord {
+    Named { name: String, score: u16 },
+    Anonymous(u16),
+}
+
+impl From<StoredRecord> for Record {
+    fn from(record: StoredRecord) -> Self {
+        match record {
+            StoredRecord::Named { name, score } => Record::new(name, score),
+            StoredRecord::Anonymous(score) => Record::new(ANONYMOUS.to_string(), score),
+        }
+    }
+}
+
+impl Record {
+    pub fn new(name: String, score: u16) -> Self {
+        Self { name, score }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_score(&self) -> u16 {
+        self.score
+    }
+}
+
+/// Where the score of a game goes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ranking {
+    /// With the other scores of its board.
+    Ranked,
+    /// With the other practice scores of its board, apart from the ranked ones.
+    Practice,
+    /// Nowhere, as when the autopilot played.
+    Unranked,
+}
 
-type ScoreboardType = HashMap<String, Vec<u16>>;
+impl Ranking {
+    /// The scoreboard a score of the scoreboard `category` goes to, if any.
+    pub fn category(&self, category: String) -> Option<String> {
+        match self {
+            Ranking::Ranked => Some(category),
+            Ranking::Practice => Some(format!("{} (practice)", category)),
+            Ranking::Unranked => None,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Scoreboard {
@@ -22,15 +86,21 @@ impl Scoreboard {
         Self { scoreboard }
     }
 
-    pub fn add(&mut self, board_name: String, score: u16) {
-        self.scoreboard.entry(board_name).or_default().push(score);
+    /// Records `score`; an empty `name` is stored as anonymous.
+    pub fn add(&mut self, board_name: String, name: String, score: u16) {
+        let name = Self::player(&name).to_string();
+
+        self.scoreboard
+            .entry(board_name)
+            .or_default()
+            .push(Record::new(name, score));
     }
 
-    pub fn get(&self, board_name: &str) -> Option<Vec<u16>> {
+    /// The records of a board, best first.
+    pub fn get(&self, board_name: &str) -> Option<Vec<Record>> {
         self.scoreboard.get(board_name).map(|s| {
             let mut s = s.clone();
-            s.sort_unstable();
-            s.reverse();
+            s.sort_by_key(|r| std::cmp::Reverse(r.score));
             s
         })
     }
@@ -38,6 +108,36 @@ impl Scoreboard {
     pub fn get_names(&self) -> Vec<String> {
         self.scoreboard.keys().cloned().collect()
     }
+
+    /// The 1-based place `score` takes on the board; ties go to the older record.
+    pub fn rank(&self, board_name: &str, score: u16) -> usize {
+        self.scoreboard
+            .get(board_name)
+            .map_or(0, |s| s.iter().filter(|r| r.score >= score).count())
+            + 1
+    }
+
+    pub fn is_high_score(&self, board_name: &str, score: u16) -> bool {
+        score > 0 && self.rank(board_name, score) <= HIGH_SCORES
+    }
+
+    pub fn personal_best(&self, board_name: &str, name: &str) -> Option<u16> {
+        self.scoreboard
+            .get(board_name)?
+            .iter()
+            .filter(|r| r.name == Self::player(name))
+            .map(|r| r.score)
+            .max()
+    }
+}
+
+impl Scoreboard {
+    fn player(name: &str) -> &str {
+        match name.trim() {
+            "" => ANONYMOUS,
+            name => name,
+        }
+    }
 }
 
 impl Drop for Scoreboard {
@@ -47,3 +147,34 @@ impl Drop for Scoreboard {
         file.write_all(json.as_bytes()).unwrap();
     }
 }
+
+#[cfg(test)]
+mod test_scoreboard {
+    use super::{Ranking, Record};
+
+    #[test]
+    fn read_records() {
+        let records: Vec<Record> =
+            serde_json::from_str(r#"[7, {"name": "ali", "score": 12}]"#).unwrap();
+
+        assert_eq!(
+            records,
+            vec![
+                Record::new("anonymous".to_string(), 7),
+                Record::new("ali".to_string(), 12)
+            ]
+        );
+    }
+
+    #[test]
+    fn practice_scores_are_kept_apart() {
+        let category = || "hard (zen)".to_string();
+
+        assert_eq!(Ranking::Ranked.category(category()), Some(category()));
+        assert_eq!(
+            Ranking::Practice.category(category()),
+            Some("hard (zen) (practice)".to_string())
+        );
+        assert_eq!(Ranking::Unranked.category(category()), None);
+    }
+}
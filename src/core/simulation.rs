@@ -0,0 +1,275 @@
+use std::time::{Duration, Instant};
+
+use super::{Board, Controller, DeathCause, Game};
+
+/// How a game played without a screen ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    seed: u64,
+    score: u16,
+    ticks: u32,
+    /// What killed the snake, `None` if it cleared the board or ran out of ticks.
+    death: Option<DeathCause>,
+    cleared: bool,
+    /// Why the controller gave up, if it did.
+    forfeit: Option<String>,
+}
+
+/// Plays single player games on a board as fast as the controller decides,
+/// without drawing anything.
+pub struct Simulation<'a> {
+    board: &'a Board,
+    length: u16,
+    max_ticks: u32,
+}
+
+impl<'a> Simulation<'a> {
+    /// Games on `board` with snakes of `length` that stop after `max_ticks`
+    /// walks at the latest, so a bot going around in circles cannot hang them.
+    pub fn new(board: &'a Board, length: u16, max_ticks: u32) -> Self {
+        Self {
+            board,
+            length,
+            max_ticks,
+        }
+    }
+
+    /// Plays the game of `seed` with `controller` steering the snake.
+    pub fn play(&self, controller: &mut dyn Controller, seed: u64) -> Outcome {
+        let mut game = Game::with_seed(self.board, self.length, seed);
+
+        while game.get_ticks() < self.max_ticks {
+            if let Some(direction) = controller.decide(&game, 0) {
+                game.rotation(direction);
+            }
+            if controller.get_forfeit().is_some() {
+                game.forfeit(0);
+                break;
+            }
+            if !game.walk() {
+                break;
+            }
+        }
+
+        Outcome {
+            seed,
+            score: game.get_score(),
+            ticks: game.get_ticks(),
+            death: game.get_death().map(|death| death.get_cause()),
+            cleared: game.is_cleared(),
+            forfeit: controller.get_forfeit().map(str::to_string),
+        }
+    }
+
+    /// Plays `games` games with the seeds from `first_seed` on, each with a
+    /// fresh controller made for its seed.
+    pub fn run<F>(&self, games: usize, first_seed: u64, mut controller: F) -> Report
+    where
+        F: FnMut(u64) -> Box<dyn Controller>,
+    {
+        let started = Instant::now();
+        let outcomes = (0..games as u64)
+            .map(|index| {
+                let seed = first_seed.wrapping_add(index);
+                self.play(controller(seed).as_mut(), seed)
+            })
+            .collect();
+
+        Report {
+            outcomes,
+            elapsed: started.elapsed(),
+        }
+    }
+}
+
+/// The lowest, median, mean and highest of some numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spread {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub max: f64,
+}
+
+impl Spread {
+    fn of(mut values: Vec<f64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+
+        let middle = values.len() / 2;
+        let median = match values.len() % 2 {
+            0 => (values[middle - 1] + values[middle]) / 2.0,
+            _ => values[middle],
+        };
+
+        Some(Self {
+            min: values[0],
+            median,
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            max: values[values.len() - 1],
+        })
+    }
+}
+
+/// Every outcome of a `Simulation::run` and how long they took.
+#[derive(Debug, Clone)]
+pub struct Report {
+    outcomes: Vec<Outcome>,
+    elapsed: Duration,
+}
+
+impl Report {
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn get_scores(&self) -> Option<Spread> {
+        Spread::of(self.outcomes.iter().map(|o| o.score as f64).collect())
+    }
+
+    /// How many ticks the snakes lived.
+    pub fn get_survival(&self) -> Option<Spread> {
+        Spread::of(self.outcomes.iter().map(|o| o.ticks as f64).collect())
+    }
+
+    /// How many games ended each way, the most common first.
+    pub fn get_endings(&self) -> Vec<(Ending, usize)> {
+        let mut endings: Vec<(Ending, usize)> = Vec::new();
+
+        for outcome in &self.outcomes {
+            let ending = match (outcome.cleared, outcome.death) {
+                (true, _) => Ending::Cleared,
+                (false, Some(cause)) => Ending::Died(cause),
+                (false, None) => Ending::OutOfTicks,
+            };
+
+            match endings.iter_mut().find(|(e, _)| *e == ending) {
+                Some((_, count)) => *count += 1,
+                None => endings.push((ending, 1)),
+            }
+        }
+        endings.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        endings
+    }
+
+    /// The seeds of the games the controller gave up and why it did.
+    pub fn get_forfeits(&self) -> Vec<(u64, &str)> {
+        self.outcomes
+            .iter()
+            .filter_map(|o| Some((o.seed, o.forfeit.as_deref()?)))
+            .collect()
+    }
+
+    pub fn get_games_per_second(&self) -> f64 {
+        self.outcomes.len() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// The ways a simulated game can end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ending {
+    Cleared,
+    Died(DeathCause),
+    OutOfTicks,
+}
+
+impl Ending {
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Self::Cleared => "cleared the board",
+            Self::Died(DeathCause::Wall) => "ran into a wall",
+            Self::Died(DeathCause::Body) => "bit its own body",
+            Self::Died(DeathCause::Snake) => "ran into another snake",
+            Self::Died(DeathCause::HeadOn) => "crashed head-on",
+            Self::Died(DeathCause::Left) => "forfeited",
+            Self::Died(DeathCause::Enemy) => "was hit by an enemy",
+            Self::OutOfTicks => "ran out of ticks",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_simulation {
+    use super::{Ending, Simulation, Spread};
+    use crate::core::{Board, DeathCause, Difficulty, Direction, Game, Wall};
+
+    #[test]
+    fn same_seeds_same_outcomes() {
+        let board = Board::new("test".to_string(), 10, Vec::from([Wall::new(2, 2)]));
+        let simulation = Simulation::new(&board, 3, 300);
+
+        let first = simulation.run(5, 1, |seed| Box::new(Difficulty::Medium.bot(seed).unwrap()));
+        let second = simulation.run(5, 1, |seed| Box::new(Difficulty::Medium.bot(seed).unwrap()));
+
+        assert_eq!(first.outcomes, second.outcomes);
+        assert_eq!(first.outcomes[4].seed, 5);
+        assert!(first.outcomes.iter().all(|o| o.ticks <= 300));
+    }
+
+    #[test]
+    fn counts_endings() {
+        struct Straight;
+        impl crate::core::Controller for Straight {
+            fn decide(&mut self, _: &Game, _: usize) -> Option<Direction> {
+                None
+            }
+        }
+
+        // a snake going right runs into the wall across its row
+        let board = Board::new("test".to_string(), 10, Vec::from([Wall::new(4, 8)]));
+        let report = Simulation::new(&board, 3, 100).run(3, 0, |_| Box::new(Straight));
+        assert_eq!(
+            report.get_endings(),
+            Vec::from([(Ending::Died(DeathCause::Wall), 3)])
+        );
+
+        let board = Board::new("test".to_string(), 10, Vec::new());
+        let report = Simulation::new(&board, 3, 100).run(2, 0, |_| Box::new(Straight));
+        assert_eq!(report.get_endings(), Vec::from([(Ending::OutOfTicks, 2)]));
+        assert_eq!(report.get_survival().unwrap().max, 100.0);
+    }
+
+    #[test]
+    fn forfeits_end_the_game() {
+        struct Quitter;
+        impl crate::core::Controller for Quitter {
+            fn decide(&mut self, _: &Game, _: usize) -> Option<Direction> {
+                None
+            }
+
+            fn get_forfeit(&self) -> Option<&str> {
+                Some("bored")
+            }
+        }
+
+        let board = Board::new("test".to_string(), 10, Vec::new());
+        let report = Simulation::new(&board, 3, 100).run(2, 4, |_| Box::new(Quitter));
+
+        assert_eq!(
+            report.get_endings(),
+            Vec::from([(Ending::Died(DeathCause::Left), 2)])
+        );
+        assert_eq!(
+            report.get_forfeits(),
+            Vec::from([(4, "bored"), (5, "bored")])
+        );
+        assert_eq!(report.get_survival().unwrap().max, 0.0);
+    }
+
+    #[test]
+    fn spread() {
+        assert_eq!(Spread::of(Vec::new()), None);
+        assert_eq!(
+            Spread::of(Vec::from([4.0, 1.0, 3.0, 8.0])),
+            Some(Spread {
+                min: 1.0,
+                median: 3.5,
+                mean: 4.0,
+                max: 8.0
+            })
+        );
+    }
+}
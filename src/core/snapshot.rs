@@ -0,0 +1,70 @@
+use super::{point::Point, Food, Mover, Player, PowerUp};
+
+use serde::{Deserialize, Serialize};
+
+/// What a game looks like after a walk: enough to draw it somewhere else.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    players: Vec<Player>,
+    food: Point,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    items: Vec<Food>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    power_ups: Vec<PowerUp>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    broken: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    movers: Vec<Mover>,
+    ticks: u32,
+}
+
+impl Snapshot {
+    pub fn new(
+        players: Vec<Player>,
+        food: Point,
+        items: Vec<Food>,
+        power_ups: Vec<PowerUp>,
+        broken: Vec<Point>,
+        movers: Vec<Mover>,
+        ticks: u32,
+    ) -> Self {
+        Self {
+            players,
+            food,
+            items,
+            power_ups,
+            broken,
+            movers,
+            ticks,
+        }
+    }
+
+    pub fn get_players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn get_food(&self) -> &Point {
+        &self.food
+    }
+
+    pub fn get_items(&self) -> &[Food] {
+        &self.items
+    }
+
+    pub fn get_power_ups(&self) -> &[PowerUp] {
+        &self.power_ups
+    }
+
+    /// The breakable walls that gave way.
+    pub fn get_broken(&self) -> &[Point] {
+        &self.broken
+    }
+
+    pub fn get_movers(&self) -> &[Mover] {
+        &self.movers
+    }
+
+    pub fn get_ticks(&self) -> u32 {
+        self.ticks
+    }
+}
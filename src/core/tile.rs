@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+use super::{point::Point, Direction};
+
+/// Portal pairs a board can have, each drawn with its own number.
+pub const PORTAL_PAIRS: u8 = 9;
+const PORTAL_GLYPHS: [&str; PORTAL_PAIRS as usize] =
+    ["@1", "@2", "@3", "@4", "@5", "@6", "@7", "@8", "@9"];
+
+/// What a cell of a board is made of.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Tile {
+    Floor,
+    Wall,
+    /// Can only be entered heading this way.
+    OneWay(Direction),
+    /// Holds the snake back for a walk after it enters.
+    Mud,
+    /// The snake cannot turn while its head is on it.
+    Ice,
+    /// Speeds the snake up for a while.
+    SpeedPad,
+    /// A wall that gives way the first time a snake runs into it.
+    Breakable,
+    /// One end of a pair of portals: a head entering it comes out of the
+    /// other end, still heading the same way.
+    Portal(u8),
+}
+
+impl Tile {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Tile::Floor => "floor",
+            Tile::Wall => "wall",
+            Tile::OneWay(Direction::Up) => "one-way up",
+            Tile::OneWay(Direction::Down) => "one-way down",
+            Tile::OneWay(Direction::Left) => "one-way left",
+            Tile::OneWay(_) => "one-way right",
+            Tile::Mud => "mud",
+            Tile::Ice => "ice",
+            Tile::SpeedPad => "speed pad",
+            Tile::Breakable => "breakable wall",
+            Tile::Portal(_) => "portal",
+        }
+    }
+
+    pub fn get_glyph(&self) -> &'static str {
+        match self {
+            Tile::Floor => "  ",
+            Tile::Wall => "██",
+            Tile::OneWay(Direction::Up) => " ↑",
+            Tile::OneWay(Direction::Down) => " ↓",
+            Tile::OneWay(Direction::Left) => " ←",
+            Tile::OneWay(_) => " →",
+            Tile::Mud => "~~",
+            Tile::Ice => "░░",
+            Tile::SpeedPad => "»»",
+            Tile::Breakable => "▒▒",
+            Tile::Portal(pair) => PORTAL_GLYPHS[(*pair % PORTAL_PAIRS) as usize],
+        }
+    }
+
+    /// Whether a snake heading `direction` may enter the tile; walls aside,
+    /// only one-way tiles turn snakes away.
+    pub fn lets_in(&self, direction: Direction) -> bool {
+        match self {
+            Tile::OneWay(way) => *way == direction,
+            _ => true,
+        }
+    }
+}
+
+/// A tile other than floor or a plain wall, where it lies on its board.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlacedTile {
+    point: Point,
+    tile: Tile,
+}
+
+impl PlacedTile {
+    pub fn new(point: Point, tile: Tile) -> Self {
+        Self { point, tile }
+    }
+
+    pub fn get_point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn get_tile(&self) -> Tile {
+        self.tile
+    }
+}
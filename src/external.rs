@@ -0,0 +1,6 @@
+mod bot;
+mod gym;
+mod protocol;
+
+pub use bot::ExternalBot;
+pub use gym::serve_gym;
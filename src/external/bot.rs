@@ -0,0 +1,168 @@
+use crate::core::{Controller, Direction, Game};
+
+use super::protocol::{BotMessage, GameMessage, State};
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// How long a bot may take to answer its first state, since that includes
+/// starting the process.
+const STARTUP_BUDGET: Duration = Duration::from_secs(1);
+
+/// A running bot process and the lines it wrote.
+struct Process {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<io::Result<String>>,
+}
+
+impl Process {
+    fn spawn(command: &[String]) -> io::Result<Self> {
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no program to run"))?;
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        // reading happens on its own thread so waiting for a line can time out
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            stdin,
+            lines,
+        })
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        self.stdin.take();
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// A bot in another program, talked to over its stdin and stdout.
+///
+/// The program gets a `GameMessage::Start` with the board, then a
+/// `GameMessage::State` before every walk, and has to answer each state
+/// with a `BotMessage` line within the budget. A program that cannot be
+/// started, answers late or answers something else forfeits the game.
+pub struct ExternalBot {
+    command: Vec<String>,
+    budget: Duration,
+    process: Option<Process>,
+    forfeit: Option<String>,
+}
+
+impl ExternalBot {
+    /// A bot running `command`, its program first and then its arguments.
+    /// The program starts with the first decision.
+    pub fn new(command: Vec<String>, budget: Duration) -> Self {
+        Self {
+            command,
+            budget,
+            process: None,
+            forfeit: None,
+        }
+    }
+
+    fn exchange(&mut self, game: &Game, player: usize) -> Result<Option<Direction>, String> {
+        let budget = match &self.process {
+            Some(_) => self.budget,
+            None => {
+                let process = Process::spawn(&self.command)
+                    .map_err(|e| format!("The bot could not be started: {e}."))?;
+                self.process = Some(process);
+                self.send(&GameMessage::Start {
+                    board: game.get_board().clone(),
+                    you: player,
+                    budget_millis: self.budget.as_millis() as u64,
+                })?;
+
+                self.budget.max(STARTUP_BUDGET)
+            }
+        };
+
+        self.send(&GameMessage::State(State::new(game)))?;
+
+        let Some(process) = &self.process else {
+            return Err("The bot is not running.".to_string());
+        };
+        let line = match process.lines.recv_timeout(budget) {
+            Ok(Ok(line)) => line,
+            Ok(Err(e)) => return Err(format!("The bot's output could not be read: {e}.")),
+            Err(RecvTimeoutError::Timeout) => {
+                return Err(format!(
+                    "The bot did not answer within {} ms.",
+                    budget.as_millis()
+                ))
+            }
+            Err(RecvTimeoutError::Disconnected) => return Err("The bot stopped.".to_string()),
+        };
+
+        serde_json::from_str::<BotMessage>(&line)
+            .map(|message| message.get_direction())
+            .map_err(|e| format!("The bot answered '{line}', which is not a move: {e}."))
+    }
+
+    fn send(&mut self, message: &GameMessage) -> Result<(), String> {
+        let stdin = self
+            .process
+            .as_mut()
+            .and_then(|process| process.stdin.as_mut())
+            .ok_or("The bot is not running.")?;
+
+        let mut line = serde_json::to_string(message).map_err(|e| e.to_string())?;
+        line.push('\n');
+
+        stdin
+            .write_all(line.as_bytes())
+            .and_then(|_| stdin.flush())
+            .map_err(|_| "The bot stopped reading.".to_string())
+    }
+}
+
+impl Controller for ExternalBot {
+    fn decide(&mut self, game: &Game, player: usize) -> Option<Direction> {
+        if self.forfeit.is_some() || !game.get_players()[player].is_alive() {
+            return None;
+        }
+
+        match self.exchange(game, player) {
+            Ok(direction) => direction,
+            Err(reason) => {
+                // the bot may still be listening, so it learns why it lost
+                let _ = self.send(&GameMessage::Forfeit {
+                    reason: reason.clone(),
+                });
+                self.forfeit = Some(reason);
+                None
+            }
+        }
+    }
+
+    fn get_forfeit(&self) -> Option<&str> {
+        self.forfeit.as_deref()
+    }
+}
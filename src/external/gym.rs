@@ -0,0 +1,86 @@
+use crate::core::{Action, Observation, Step, VecEnv};
+
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+/// What a trainer sends to the gym, one JSON object per line.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GymRequest {
+    /// Starts every env over, from `seed` on.
+    Reset { seed: u64 },
+    /// Steps the `i`-th env with the `i`-th action.
+    Step { actions: Vec<Action> },
+}
+
+/// What the gym answers to every request, one JSON object per line.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GymResponse {
+    Reset { observations: Vec<Observation> },
+    Step { steps: Vec<Step> },
+    Error { message: String },
+}
+
+/// Answers the requests of a trainer in `input` on `output` until it
+/// closes `input`.
+pub fn serve_gym(envs: &mut VecEnv, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str(&line) {
+            Ok(GymRequest::Reset { seed }) => GymResponse::Reset {
+                observations: envs.reset(seed),
+            },
+            Ok(GymRequest::Step { actions }) => GymResponse::Step {
+                steps: envs.step(&actions),
+            },
+            Err(e) => GymResponse::Error {
+                message: e.to_string(),
+            },
+        };
+
+        serde_json::to_writer(&mut output, &response)?;
+        output.write_all(b"\n")?;
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_gym {
+    use super::{serve_gym, GymResponse};
+    use crate::core::{Board, Config, VecEnv};
+
+    #[test]
+    fn answers_every_line() {
+        let board = Board::new("test".to_string(), 5, Vec::new());
+        let mut envs = VecEnv::new(&board, Config::default(), 2);
+        let input = concat!(
+            r#"{"type":"reset","seed":3}"#,
+            "\n",
+            r#"{"type":"step","actions":["TurnLeft","Up"]}"#,
+            "\n",
+            r#"{"type":"dance"}"#,
+            "\n",
+        );
+        let mut output = Vec::new();
+
+        serve_gym(&mut envs, input.as_bytes(), &mut output).unwrap();
+        let responses: Vec<GymResponse> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert!(
+            matches!(&responses[0], GymResponse::Reset { observations } if observations.len() == 2)
+        );
+        assert!(matches!(&responses[1], GymResponse::Step { steps } if steps[1].info.seed == 4));
+        assert!(matches!(&responses[2], GymResponse::Error { .. }));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::core::{Board, Direction, Food, Game, Point, PowerUp};
+
+use serde::{Deserialize, Serialize};
+
+/// What the game sends to an external bot, one JSON object per line.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameMessage {
+    /// Sent once, before the first state.
+    Start {
+        board: Board,
+        /// The bot's snake in every state's `snakes`.
+        you: usize,
+        /// How long the bot may think about each state.
+        budget_millis: u64,
+    },
+    /// The game before a walk; the bot answers it with a `BotMessage`.
+    State(State),
+    /// The bot broke the protocol or took too long, so its snake left the game.
+    Forfeit { reason: String },
+}
+
+/// What an external bot answers to every state, one JSON object per line.
+///
+/// Without a direction the snake keeps going the way it does; turning back
+/// into its own neck is ignored like it is for a player.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotMessage {
+    #[serde(default)]
+    direction: Option<Direction>,
+}
+
+impl BotMessage {
+    pub fn get_direction(&self) -> Option<Direction> {
+        self.direction
+            .filter(|direction| *direction != Direction::None)
+    }
+}
+
+/// Everything a bot needs to know about the game after a walk.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct State {
+    tick: u32,
+    snakes: Vec<SnakeState>,
+    food: Point,
+    /// Bonus, poison and golden food, on boards that have any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    items: Vec<Food>,
+    /// Power-ups, on boards that have any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    power_ups: Vec<PowerUp>,
+    /// Where the moving obstacles and enemies of the board are.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    entities: Vec<Point>,
+}
+
+impl State {
+    pub fn new(game: &Game) -> Self {
+        Self {
+            tick: game.get_ticks(),
+            snakes: game
+                .get_players()
+                .iter()
+                .map(|player| SnakeState {
+                    body: player.get_snake().iter().cloned().collect(),
+                    direction: player.get_direction(),
+                    score: player.get_score(),
+                    alive: player.is_alive(),
+                })
+                .collect(),
+            food: game.get_food().clone(),
+            items: game.get_items().to_vec(),
+            power_ups: game.get_power_ups().to_vec(),
+            entities: game
+                .get_movers()
+                .iter()
+                .map(|mover| mover.get_point().clone())
+                .collect(),
+        }
+    }
+}
+
+/// One snake of a `State`, its head first.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SnakeState {
+    body: Vec<Point>,
+    direction: Direction,
+    score: u16,
+    alive: bool,
+}
+
+#[cfg(test)]
+mod test_protocol {
+    use super::{BotMessage, GameMessage, State};
+    use crate::core::{Board, Direction, Game};
+
+    #[test]
+    fn messages_are_json_lines() {
+        let board = Board::new("test".to_string(), 5, Vec::new());
+        let game = Game::with_seed(&board, 2, 3);
+
+        let line = serde_json::to_string(&GameMessage::State(State::new(&game))).unwrap();
+        assert!(line.starts_with(r#"{"type":"state","tick":0,"snakes":[{"body":[{"x":2,"y":2},{"x":2,"y":1}],"direction":"Right","score":0,"alive":true}]"#));
+
+        let reply: BotMessage = serde_json::from_str(r#"{"direction":"Up"}"#).unwrap();
+        assert_eq!(reply.get_direction(), Some(Direction::Up));
+        let reply: BotMessage = serde_json::from_str("{}").unwrap();
+        assert_eq!(reply.get_direction(), None);
+        assert!(serde_json::from_str::<BotMessage>(r#"{"direction":"North"}"#).is_err());
+    }
+}
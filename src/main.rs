@@ -1,12 +1,601 @@
 mod core;
+mod external;
+mod net;
 mod tui;
 
+use core::{
+    Board, Boards, Bot, Config, Controller, Cycle, Difficulty, Game, Genome, Hamiltonian, Reward,
+    Simulation, Trainer, Training, VecEnv, View, GENOME_PATH,
+};
+use external::{serve_gym, ExternalBot};
+use net::{Client, Server};
+use tokio::{net::TcpListener, time::Duration};
 use tui::Tui;
 
+const DEFAULT_PORT: u16 = 7878;
+const DEFAULT_TICK_MILLIS: u64 = 90;
+const SOLVER_SNAKE_LENGTH: u16 = 3;
+const DEFAULT_BENCH_GAMES: usize = 100;
+const DEFAULT_BENCH_TICKS: u32 = 20_000;
+const DEFAULT_BOT_BUDGET_MILLIS: u64 = 100;
+const DEFAULT_GYM_ENVS: usize = 1;
+const DEFAULT_GENERATIONS: u32 = 50;
+const BOTS: [&str; 5] = ["easy", "medium", "hard", "trained", "hamiltonian"];
+const USAGE: &str = "Usage:
+  snake_game                                  play on this terminal
+  snake_game serve [--port PORT] [--tick MS]  host games for other terminals
+  snake_game join ADDRESS [--name NAME]       play on a server, e.g. 127.0.0.1:7878
+  snake_game solve [--board NAME] [--seed N]  try to fill every board, or one of them
+  snake_game bench [--board NAME] [--bot BOT] [--games N] [--seed N] [--ticks N]
+                   [--program COMMAND] [--budget MS]
+                                              play bots headlessly and report how they did,
+                                              BOT is easy, medium, hard, trained or hamiltonian,
+                                              COMMAND runs a bot of your own instead
+  snake_game gym [--board NAME] [--envs N] [--ticks N] [--radius R] [--reward JSON]
+                                              train agents over stdin and stdout
+  snake_game train [--board NAME]... [--generations N] [--population N] [--games N]
+                   [--ticks N] [--seed N] [--from FILE] [--out FILE]
+                                              evolve the bots of the trained difficulty";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Play,
+    Serve { port: u16, tick: Duration },
+    Join { address: String, name: String },
+    Solve { board: Option<String>, seed: u64 },
+    Bench(Bench),
+    Gym(Gym),
+    Train(Train),
+}
+
+/// What the `bench` command plays.
+#[derive(Debug, PartialEq)]
+struct Bench {
+    board: Option<String>,
+    bot: String,
+    games: usize,
+    seed: u64,
+    ticks: u32,
+    /// The command line of an external bot, played instead of `bot`.
+    program: Option<String>,
+    /// How long an external bot may think about each tick.
+    budget: Duration,
+}
+
+/// What the `gym` command serves.
+#[derive(Debug, PartialEq)]
+struct Gym {
+    board: Option<String>,
+    envs: usize,
+    config: Config,
+}
+
+/// How the `train` command evolves its bots.
+#[derive(Debug, PartialEq)]
+struct Train {
+    boards: Vec<String>,
+    generations: u32,
+    training: Training,
+    seed: u64,
+    /// A genome to go on from instead of random ones.
+    from: Option<String>,
+    out: String,
+}
+
+impl Command {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let Some((command, rest)) = args.split_first() else {
+            return Ok(Self::Play);
+        };
+
+        match command.as_str() {
+            "serve" => {
+                let mut port = DEFAULT_PORT;
+                let mut tick = DEFAULT_TICK_MILLIS;
+                for (flag, value) in Self::flags(rest)? {
+                    match flag {
+                        "--port" => port = Self::number(flag, value)?,
+                        "--tick" => tick = Self::number(flag, value)?,
+                        _ => return Err(format!("Unknown option '{flag}'.")),
+                    }
+                }
+                if tick == 0 {
+                    return Err("'--tick' must be at least 1.".to_string());
+                }
+
+                Ok(Self::Serve {
+                    port,
+                    tick: Duration::from_millis(tick),
+                })
+            }
+            "join" => {
+                let Some((address, rest)) = rest.split_first() else {
+                    return Err("'join' needs the address of a server.".to_string());
+                };
+
+                let mut name = String::new();
+                for (flag, value) in Self::flags(rest)? {
+                    match flag {
+                        "--name" => name = value.to_string(),
+                        _ => return Err(format!("Unknown option '{flag}'.")),
+                    }
+                }
+
+                Ok(Self::Join {
+                    address: address.to_string(),
+                    name,
+                })
+            }
+            "solve" => {
+                let mut board = None;
+                let mut seed = 0;
+                for (flag, value) in Self::flags(rest)? {
+                    match flag {
+                        "--board" => board = Some(value.to_string()),
+                        "--seed" => seed = Self::number(flag, value)?,
+                        _ => return Err(format!("Unknown option '{flag}'.")),
+                    }
+                }
+
+                Ok(Self::Solve { board, seed })
+            }
+            "bench" => {
+                let mut bench = Bench {
+                    board: None,
+                    bot: "hard".to_string(),
+                    games: DEFAULT_BENCH_GAMES,
+                    seed: 0,
+                    ticks: DEFAULT_BENCH_TICKS,
+                    program: None,
+                    budget: Duration::from_millis(DEFAULT_BOT_BUDGET_MILLIS),
+                };
+                for (flag, value) in Self::flags(rest)? {
+                    match flag {
+                        "--board" => bench.board = Some(value.to_string()),
+                        "--bot" if BOTS.contains(&value) => bench.bot = value.to_string(),
+                        "--bot" => return Err(format!("Unknown bot '{value}'.")),
+                        "--games" => bench.games = Self::number(flag, value)?,
+                        "--seed" => bench.seed = Self::number(flag, value)?,
+                        "--ticks" => bench.ticks = Self::number(flag, value)?,
+                        "--program" => bench.program = Some(value.to_string()),
+                        "--budget" => {
+                            bench.budget = Duration::from_millis(Self::number(flag, value)?)
+                        }
+                        _ => return Err(format!("Unknown option '{flag}'.")),
+                    }
+                }
+
+                Ok(Self::Bench(bench))
+            }
+            "gym" => {
+                let mut gym = Gym {
+                    board: None,
+                    envs: DEFAULT_GYM_ENVS,
+                    config: Config::default(),
+                };
+                for (flag, value) in Self::flags(rest)? {
+                    match flag {
+                        "--board" => gym.board = Some(value.to_string()),
+                        "--envs" => gym.envs = Self::number(flag, value)?,
+                        "--ticks" => gym.config.max_ticks = Self::number(flag, value)?,
+                        "--radius" => {
+                            gym.config.view = View::Egocentric {
+                                radius: Self::number(flag, value)?,
+                            }
+                        }
+                        "--reward" => {
+                            gym.config.reward = serde_json::from_str::<Reward>(value)
+                                .map_err(|e| format!("Option '{flag}' needs a reward: {e}."))?
+                        }
+                        _ => return Err(format!("Unknown option '{flag}'.")),
+                    }
+                }
+
+                Ok(Self::Gym(gym))
+            }
+            "train" => {
+                let mut train = Train {
+                    boards: Vec::new(),
+                    generations: DEFAULT_GENERATIONS,
+                    training: Training::default(),
+                    seed: 0,
+                    from: None,
+                    out: GENOME_PATH.to_string(),
+                };
+                for (flag, value) in Self::flags(rest)? {
+                    match flag {
+                        "--board" => train.boards.push(value.to_string()),
+                        "--generations" => train.generations = Self::number(flag, value)?,
+                        "--population" => train.training.population = Self::number(flag, value)?,
+                        "--games" => train.training.games = Self::number(flag, value)?,
+                        "--ticks" => train.training.max_ticks = Self::number(flag, value)?,
+                        "--seed" => train.seed = Self::number(flag, value)?,
+                        "--from" => train.from = Some(value.to_string()),
+                        "--out" => train.out = value.to_string(),
+                        _ => return Err(format!("Unknown option '{flag}'.")),
+                    }
+                }
+
+                Ok(Self::Train(train))
+            }
+            _ => Err(format!("Unknown command '{command}'.")),
+        }
+    }
+
+    fn flags(args: &[String]) -> Result<Vec<(&str, &str)>, String> {
+        args.chunks(2)
+            .map(|pair| match pair {
+                [flag, value] => Ok((flag.as_str(), value.as_str())),
+                [flag] => Err(format!("Option '{flag}' needs a value.")),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+        value
+            .parse()
+            .map_err(|_| format!("Option '{flag}' needs a number, not '{value}'."))
+    }
+}
+
+async fn serve(port: u16, tick: Duration) -> std::io::Result<()> {
+    let boards = Boards::new().get_all().to_vec();
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    println!("Serving snake games on {}", listener.local_addr()?);
+
+    Server::new(boards, tick).run(listener).await
+}
+
+async fn join(address: String, name: String) -> std::io::Result<()> {
+    let client = Client::join(address, &name).await?;
+
+    Tui::join(client).await
+}
+
+/// Plays `board` with a `Hamiltonian` bot and tells how it went.
+fn solve_board(board: &Board, seed: u64) -> String {
+    let cycle = match Cycle::find(board) {
+        Ok(cycle) => cycle,
+        Err(reason) => return format!("no cycle: {}", reason.describe()),
+    };
+    // following the cycle eats within one lap, so a longer game is stuck
+    let limit = cycle.len() * cycle.len() * 2;
+    let mut bot = Hamiltonian::new(cycle);
+    let mut game = Game::with_seed(board, SOLVER_SNAKE_LENGTH, seed);
+
+    while game.get_ticks() as usize <= limit {
+        if let Some(direction) = bot.decide(&game, 0) {
+            game.rotation(direction);
+        }
+        if !game.walk() {
+            break;
+        }
+    }
+
+    match (game.is_cleared(), game.get_death()) {
+        (true, _) => format!("cleared in {} ticks", game.get_ticks()),
+        (false, Some(death)) => format!(
+            "died after {} points: {}",
+            game.get_score(),
+            death.describe()
+        ),
+        (false, None) => format!("gave up after {} ticks", game.get_ticks()),
+    }
+}
+
+fn solve(name: Option<String>, seed: u64) -> std::io::Result<()> {
+    let boards = Boards::new();
+    let boards: Vec<&Board> = boards
+        .get_all()
+        .iter()
+        .filter(|board| name.as_ref().is_none_or(|name| board.get_name() == name))
+        .collect();
+
+    if boards.is_empty() {
+        println!("There is no board named '{}'.", name.unwrap_or_default());
+    }
+    for board in boards {
+        println!("{}: {}", board.get_name(), solve_board(board, seed));
+    }
+
+    Ok(())
+}
+
+type BotFactory = Box<dyn FnMut(u64) -> Box<dyn Controller>>;
+
+/// Makes a fresh controller of the bench's bot for every seed.
+fn bench_bot(bench: &Bench, board: &Board) -> Result<BotFactory, String> {
+    if let Some(program) = &bench.program {
+        let command: Vec<String> = program.split_whitespace().map(str::to_string).collect();
+        let budget = bench.budget;
+
+        return Ok(Box::new(move |_| {
+            Box::new(ExternalBot::new(command.clone(), budget))
+        }));
+    }
+
+    let difficulty = match bench.bot.as_str() {
+        "easy" => Difficulty::Easy,
+        "medium" => Difficulty::Medium,
+        "hard" => Difficulty::Hard,
+        "trained" => {
+            let genome = Genome::load(GENOME_PATH)
+                .map_err(|error| format!("cannot load {GENOME_PATH}: {error}"))?;
+            return Ok(Box::new(move |seed| {
+                Box::new(Bot::trained(genome.clone(), seed))
+            }));
+        }
+        _ => {
+            let cycle = Cycle::find(board).map_err(|reason| reason.describe())?;
+            return Ok(Box::new(move |_| Box::new(Hamiltonian::new(cycle.clone()))));
+        }
+    };
+
+    Ok(Box::new(move |seed| {
+        let bot = difficulty.bot(seed);
+        Box::new(bot.expect("only a trained bot needs a genome"))
+    }))
+}
+
+fn bench(bench: Bench) -> std::io::Result<()> {
+    let boards = Boards::new();
+    let board = match &bench.board {
+        Some(name) => boards
+            .get_all()
+            .iter()
+            .find(|board| board.get_name() == name),
+        None => boards.get(0),
+    };
+    let Some(board) = board else {
+        println!(
+            "There is no board named '{}'.",
+            bench.board.unwrap_or_default()
+        );
+        return Ok(());
+    };
+
+    let name = bench.program.as_deref().unwrap_or(&bench.bot);
+    let bot = bench_bot(&bench, board).map_err(|error| {
+        std::io::Error::other(format!(
+            "The {} bot cannot play {}: {error}",
+            name,
+            board.get_name()
+        ))
+    })?;
+
+    let report =
+        Simulation::new(board, SOLVER_SNAKE_LENGTH, bench.ticks).run(bench.games, bench.seed, bot);
+
+    println!(
+        "{} games of the {} bot on {}",
+        bench.games,
+        name,
+        board.get_name()
+    );
+    if let (Some(scores), Some(survival)) = (report.get_scores(), report.get_survival()) {
+        println!(
+            "score:    min {} median {} mean {:.1} max {}",
+            scores.min, scores.median, scores.mean, scores.max
+        );
+        println!(
+            "survival: min {} median {} mean {:.1} max {} ticks",
+            survival.min, survival.median, survival.mean, survival.max
+        );
+    }
+    for (ending, count) in report.get_endings() {
+        println!("{count:>5} {}", ending.get_name());
+    }
+    if let Some((seed, reason)) = report.get_forfeits().first() {
+        println!("the game of seed {seed} was forfeited: {reason}");
+    }
+    println!(
+        "{:.1} games/s ({:.2?} in total)",
+        report.get_games_per_second(),
+        report.get_elapsed()
+    );
+
+    Ok(())
+}
+
+fn gym(gym: Gym) -> std::io::Result<()> {
+    let boards = Boards::new();
+    let board = match &gym.board {
+        Some(name) => boards
+            .get_all()
+            .iter()
+            .find(|board| board.get_name() == name),
+        None => boards.get(0),
+    };
+    let Some(board) = board else {
+        eprintln!(
+            "There is no board named '{}'.",
+            gym.board.unwrap_or_default()
+        );
+        return Ok(());
+    };
+
+    let mut envs = VecEnv::new(board, gym.config, gym.envs);
+
+    serve_gym(&mut envs, std::io::stdin().lock(), std::io::stdout().lock())
+}
+
+fn train(train: Train) -> std::io::Result<()> {
+    let boards = Boards::new();
+    let chosen: Vec<&Board> = boards
+        .get_all()
+        .iter()
+        .filter(|board| {
+            train.boards.is_empty() || train.boards.iter().any(|n| n == board.get_name())
+        })
+        .collect();
+    if chosen.len() < train.boards.len().max(1) {
+        println!("Some of the boards {:?} do not exist.", train.boards);
+        return Ok(());
+    }
+
+    let mut trainer = match &train.from {
+        Some(path) => Trainer::from_genome(chosen, train.training, train.seed, Genome::load(path)?),
+        None => Trainer::new(chosen, train.training, train.seed),
+    };
+
+    for _ in 0..train.generations {
+        let generation = trainer.evolve();
+        println!(
+            "generation {:>3}: best {:.2}, mean {:.2}",
+            generation.index, generation.best, generation.mean
+        );
+
+        // saved every generation, so stopping the training early loses nothing
+        if let Some((genome, _)) = trainer.get_best() {
+            genome.save(&train.out)?;
+        }
+    }
+
+    if let Some((_, fitness)) = trainer.get_best() {
+        println!(
+            "The best genome, of fitness {fitness:.2}, is in {}.",
+            train.out
+        );
+    }
+
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() {
-    match Tui::render().await {
-        Ok(_) => {}
-        Err(e) => println!("{e}"),
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match Command::parse(&args) {
+        Ok(Command::Play) => Tui::render().await,
+        Ok(Command::Serve { port, tick }) => serve(port, tick).await,
+        Ok(Command::Join { address, name }) => join(address, name).await,
+        Ok(Command::Solve { board, seed }) => solve(board, seed),
+        Ok(Command::Bench(options)) => bench(options),
+        Ok(Command::Gym(options)) => gym(options),
+        Ok(Command::Train(options)) => train(options),
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test_main {
+    use super::{solve_board, Bench, Command, Gym, Train};
+    use crate::core::{Board, Config, Reward, Training, View};
+    use tokio::time::Duration;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        Command::parse(&args)
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(parse(&[]), Ok(Command::Play));
+        assert_eq!(
+            parse(&["serve", "--port", "9000"]),
+            Ok(Command::Serve {
+                port: 9000,
+                tick: Duration::from_millis(90)
+            })
+        );
+        assert_eq!(
+            parse(&["join", "127.0.0.1:9000", "--name", "ali"]),
+            Ok(Command::Join {
+                address: "127.0.0.1:9000".to_string(),
+                name: "ali".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&["solve", "--board", "easy", "--seed", "4"]),
+            Ok(Command::Solve {
+                board: Some("easy".to_string()),
+                seed: 4
+            })
+        );
+        assert_eq!(
+            parse(&["bench", "--bot", "easy", "--games", "10"]),
+            Ok(Command::Bench(Bench {
+                board: None,
+                bot: "easy".to_string(),
+                games: 10,
+                seed: 0,
+                ticks: 20_000,
+                program: None,
+                budget: Duration::from_millis(100)
+            }))
+        );
+        assert!(parse(&["bench", "--bot", "clever"]).is_err());
+        assert_eq!(
+            parse(&[
+                "gym",
+                "--envs",
+                "8",
+                "--radius",
+                "4",
+                "--reward",
+                r#"{"step":-0.5}"#
+            ]),
+            Ok(Command::Gym(Gym {
+                board: None,
+                envs: 8,
+                config: Config {
+                    view: View::Egocentric { radius: 4 },
+                    reward: Reward {
+                        step: -0.5,
+                        ..Reward::default()
+                    },
+                    ..Config::default()
+                }
+            }))
+        );
+        assert!(parse(&["gym", "--reward", "lots"]).is_err());
+        assert_eq!(
+            parse(&[
+                "train",
+                "--board",
+                "a",
+                "--board",
+                "b",
+                "--out",
+                "best.json"
+            ]),
+            Ok(Command::Train(Train {
+                boards: Vec::from(["a".to_string(), "b".to_string()]),
+                generations: 50,
+                training: Training::default(),
+                seed: 0,
+                from: None,
+                out: "best.json".to_string()
+            }))
+        );
+        assert!(parse(&["join"]).is_err());
+        assert!(parse(&["serve", "--port"]).is_err());
+        assert!(parse(&["serve", "--port", "x"]).is_err());
+        assert!(parse(&["serve", "--tick", "0"]).is_err());
+        assert!(parse(&["dance"]).is_err());
+    }
+
+    #[test]
+    fn solves_every_shipped_board() {
+        let boards: Vec<Board> = serde_json::from_str(include_str!("boards.json")).unwrap();
+
+        for board in &boards {
+            let result = solve_board(board, 1);
+            assert!(
+                result.starts_with("cleared") || result.starts_with("no cycle"),
+                "{}: {}",
+                board.get_name(),
+                result
+            );
+        }
     }
 }
@@ -0,0 +1,8 @@
+mod client;
+mod connection;
+mod protocol;
+mod server;
+
+pub use client::Client;
+pub use protocol::{ClientMessage, Lobby, ServerMessage};
+pub use server::Server;
@@ -0,0 +1,38 @@
+use super::{
+    connection::{self, Reader, Writer},
+    ClientMessage, ServerMessage,
+};
+
+use std::io;
+use tokio::net::{TcpStream, ToSocketAddrs};
+
+/// A player's connection to a `Server`.
+pub struct Client {
+    reader: Reader,
+    writer: Writer,
+}
+
+impl Client {
+    /// Connects to the server at `address` and joins its lobby as `name`.
+    pub async fn join(address: impl ToSocketAddrs, name: &str) -> io::Result<Self> {
+        let (reader, writer) = connection::split(TcpStream::connect(address).await?);
+        let mut client = Self { reader, writer };
+
+        client
+            .send(&ClientMessage::Join {
+                name: name.to_string(),
+            })
+            .await?;
+
+        Ok(client)
+    }
+
+    pub async fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        self.writer.send(message).await
+    }
+
+    /// The next message of the server, or `None` once it went away.
+    pub async fn receive(&mut self) -> io::Result<Option<ServerMessage>> {
+        self.reader.receive().await
+    }
+}
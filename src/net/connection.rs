@@ -0,0 +1,51 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::io;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+};
+
+/// The receiving end of a connection carrying one JSON message per line.
+pub struct Reader {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+}
+
+/// The sending end of a connection carrying one JSON message per line.
+pub struct Writer {
+    stream: OwnedWriteHalf,
+}
+
+pub fn split(stream: TcpStream) -> (Reader, Writer) {
+    let (read, write) = stream.into_split();
+
+    (
+        Reader {
+            lines: BufReader::new(read).lines(),
+        },
+        Writer { stream: write },
+    )
+}
+
+impl Reader {
+    /// The next message, or `None` once the other side closed the connection.
+    pub async fn receive<T: DeserializeOwned>(&mut self) -> io::Result<Option<T>> {
+        match self.lines.next_line().await? {
+            Some(line) => serde_json::from_str(&line)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Writer {
+    pub async fn send<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+
+        self.stream.write_all(line.as_bytes()).await
+    }
+}
@@ -0,0 +1,95 @@
+use crate::core::{Board, Direction, Snapshot};
+
+use serde::{Deserialize, Serialize};
+
+/// What a player sends to the server, one JSON object per line.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Join {
+        name: String,
+    },
+    /// Host only: the board of the next game.
+    SelectBoard {
+        index: usize,
+    },
+    /// Host only: starts a game with everyone in the lobby.
+    Start,
+    Turn {
+        direction: Direction,
+    },
+}
+
+/// What the server sends to a player, one JSON object per line.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Lobby(Lobby),
+    Start {
+        board: Board,
+        players: Vec<String>,
+        you: usize,
+    },
+    Snapshot(Snapshot),
+    GameOver {
+        winner: Option<usize>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Who is waiting for the next game and on which board it will be played.
+///
+/// The first player to join is the host.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Lobby {
+    players: Vec<String>,
+    you: usize,
+    boards: Vec<String>,
+    selected: usize,
+    board: Board,
+}
+
+impl Lobby {
+    pub fn new(
+        players: Vec<String>,
+        you: usize,
+        boards: Vec<String>,
+        selected: usize,
+        board: Board,
+    ) -> Self {
+        Self {
+            players,
+            you,
+            boards,
+            selected,
+            board,
+        }
+    }
+
+    pub fn get_players(&self) -> &[String] {
+        &self.players
+    }
+
+    /// This player's place in `get_players`.
+    pub fn get_you(&self) -> usize {
+        self.you
+    }
+
+    pub fn is_host(&self) -> bool {
+        self.you == 0
+    }
+
+    pub fn get_boards(&self) -> &[String] {
+        &self.boards
+    }
+
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+}
@@ -0,0 +1,531 @@
+use super::{
+    connection::{self, Reader},
+    ClientMessage, Lobby, ServerMessage,
+};
+use crate::core::{Board, Game};
+
+use std::io;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc::{self, error::TrySendError, Sender, UnboundedSender},
+    time::{interval, Duration, MissedTickBehavior},
+};
+
+const MAX_PLAYERS: usize = 4;
+const SNAKE_LENGTH: u16 = 3;
+/// Messages a client may fall behind on before it is disconnected.
+const OUTBOX_SIZE: usize = 64;
+
+enum Event {
+    Message(usize, ClientMessage),
+    Disconnected(usize),
+}
+
+struct Connection {
+    id: usize,
+    name: Option<String>,
+    sender: Sender<ServerMessage>,
+}
+
+/// Hosts games for players connecting over TCP.
+///
+/// The server owns the only real `Game`: players send their turns and get
+/// a snapshot back after every walk.
+pub struct Server {
+    boards: Vec<Board>,
+    tick: Duration,
+}
+
+/// Everything the server knows between two events.
+struct Room<'a> {
+    boards: &'a [Board],
+    connections: Vec<Connection>,
+    next_id: usize,
+    selected: usize,
+    game: Option<Game<'a>>,
+    /// The connection playing each snake of `game`.
+    seats: Vec<usize>,
+}
+
+impl Server {
+    pub fn new(boards: Vec<Board>, tick: Duration) -> Self {
+        Self { boards, tick }
+    }
+
+    /// Accepts players on `listener` and hosts their games. A connection
+    /// that cannot be accepted is reported and skipped. Fails straight away
+    /// when there is no board to play on.
+    pub async fn run(&self, listener: TcpListener) -> io::Result<()> {
+        if self.boards.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "There are no boards to play on.",
+            ));
+        }
+
+        let (events_sender, mut events) = mpsc::unbounded_channel();
+        let mut room = Room::new(&self.boards);
+        let mut tick = interval(self.tick);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => room.connect(stream, events_sender.clone()),
+                    Err(error) => eprintln!("Could not accept a connection: {error}"),
+                },
+                Some(event) = events.recv() => {
+                    if room.handle(event) {
+                        tick.reset();
+                    }
+                }
+                _ = tick.tick(), if room.game.is_some() => room.tick(),
+            }
+        }
+    }
+}
+
+/// Forwards the messages of connection `id` until it closes or sends
+/// something that is not a `ClientMessage`.
+async fn listen(id: usize, mut reader: Reader, events: UnboundedSender<Event>) {
+    while let Ok(Some(message)) = reader.receive().await {
+        if events.send(Event::Message(id, message)).is_err() {
+            return;
+        }
+    }
+
+    let _ = events.send(Event::Disconnected(id));
+}
+
+impl<'a> Room<'a> {
+    fn new(boards: &'a [Board]) -> Self {
+        Self {
+            boards,
+            connections: Vec::new(),
+            next_id: 0,
+            selected: 0,
+            game: None,
+            seats: Vec::new(),
+        }
+    }
+
+    fn connect(&mut self, stream: TcpStream, events: UnboundedSender<Event>) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let (reader, mut writer) = connection::split(stream);
+        let (sender, mut outbox) = mpsc::channel::<ServerMessage>(OUTBOX_SIZE);
+
+        tokio::spawn(async move {
+            while let Some(message) = outbox.recv().await {
+                if writer.send(&message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        tokio::spawn(listen(id, reader, events));
+
+        self.connections.push(Connection {
+            id,
+            name: None,
+            sender,
+        });
+    }
+
+    /// Applies `event` and returns whether it started a game.
+    fn handle(&mut self, event: Event) -> bool {
+        match event {
+            Event::Message(id, message) => self.message(id, message),
+            Event::Disconnected(id) => {
+                self.disconnect(id);
+                false
+            }
+        }
+    }
+
+    fn message(&mut self, id: usize, message: ClientMessage) -> bool {
+        match message {
+            ClientMessage::Join { name } => self.join(id, name),
+            ClientMessage::SelectBoard { index } => match self.check_host(id) {
+                Ok(()) if index < self.boards.len() => {
+                    self.selected = index;
+                    self.broadcast_lobby();
+                }
+                Ok(()) => self.send(id, Self::error("There is no such board.")),
+                Err(error) => self.send(id, Self::error(error)),
+            },
+            ClientMessage::Start => match self.check_host(id) {
+                Ok(()) => {
+                    self.start();
+                    return true;
+                }
+                Err(error) => self.send(id, Self::error(error)),
+            },
+            ClientMessage::Turn { direction } => {
+                let seat = self.seats.iter().position(|seat| *seat == id);
+                if let (Some(game), Some(seat)) = (self.game.as_mut(), seat) {
+                    game.rotate(seat, direction);
+                }
+            }
+        }
+
+        false
+    }
+
+    fn join(&mut self, id: usize, name: String) {
+        let players = self.players().len();
+        let error = if self.game.is_some() {
+            Some("A game is already running, try again later.")
+        } else if players >= MAX_PLAYERS {
+            Some("The lobby is full.")
+        } else {
+            None
+        };
+
+        if let Some(error) = error {
+            self.send(id, Self::error(error));
+            self.connections.retain(|connection| connection.id != id);
+            return;
+        }
+
+        let Some(connection) = self.connections.iter_mut().find(|c| c.id == id) else {
+            return;
+        };
+        if connection.name.is_some() {
+            return;
+        }
+
+        let name = name.trim();
+        connection.name = Some(match name.is_empty() {
+            true => format!("player {}", players + 1),
+            false => name.to_string(),
+        });
+        self.broadcast_lobby();
+    }
+
+    fn disconnect(&mut self, id: usize) {
+        self.connections.retain(|connection| connection.id != id);
+
+        match (self.game.as_mut(), self.seats.iter().position(|s| *s == id)) {
+            (Some(game), Some(seat)) => game.forfeit(seat),
+            (None, _) => self.broadcast_lobby(),
+            _ => {}
+        }
+    }
+
+    /// Only the host may choose the board and start, and only in the lobby.
+    fn check_host(&self, id: usize) -> Result<(), &'static str> {
+        if self.game.is_some() {
+            Err("A game is already running.")
+        } else if self.players().first().map(|c| c.id) != Some(id) {
+            Err("Only the host can do that.")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn start(&mut self) {
+        let boards = self.boards;
+        let board = &boards[self.selected];
+        self.seats = self.players().iter().map(|c| c.id).collect();
+        self.game = Some(Game::with_players(
+            board,
+            SNAKE_LENGTH,
+            rand::random(),
+            self.seats.len(),
+        ));
+
+        let names = self.names();
+        for (you, id) in self.seats.clone().into_iter().enumerate() {
+            self.send(
+                id,
+                ServerMessage::Start {
+                    board: board.clone(),
+                    players: names.clone(),
+                    you,
+                },
+            );
+        }
+    }
+
+    fn tick(&mut self) {
+        let Some(game) = self.game.as_mut() else {
+            return;
+        };
+
+        let running = game.walk();
+        let snapshot = ServerMessage::Snapshot(game.snapshot());
+        let winner = game.get_winner();
+        self.broadcast(snapshot);
+
+        if !running {
+            self.game = None;
+            self.seats.clear();
+            self.broadcast(ServerMessage::GameOver { winner });
+            self.broadcast_lobby();
+        }
+    }
+
+    /// Connections that joined the lobby, the host first.
+    fn players(&self) -> Vec<&Connection> {
+        self.connections
+            .iter()
+            .filter(|connection| connection.name.is_some())
+            .collect()
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.players()
+            .iter()
+            .filter_map(|connection| connection.name.clone())
+            .collect()
+    }
+
+    fn broadcast_lobby(&mut self) {
+        let names = self.names();
+        let boards: Vec<String> = self
+            .boards
+            .iter()
+            .map(|board| board.get_name().to_string())
+            .collect();
+
+        let behind: Vec<usize> = self
+            .players()
+            .iter()
+            .enumerate()
+            .filter(|(you, connection)| {
+                let lobby = Lobby::new(
+                    names.clone(),
+                    *you,
+                    boards.clone(),
+                    self.selected,
+                    self.boards[self.selected].clone(),
+                );
+                Self::is_behind(connection, ServerMessage::Lobby(lobby))
+            })
+            .map(|(_, connection)| connection.id)
+            .collect();
+        self.disconnect_all(behind);
+    }
+
+    fn broadcast(&mut self, message: ServerMessage) {
+        let behind: Vec<usize> = self
+            .players()
+            .iter()
+            .filter(|connection| Self::is_behind(connection, message.clone()))
+            .map(|connection| connection.id)
+            .collect();
+        self.disconnect_all(behind);
+    }
+
+    fn send(&mut self, id: usize, message: ServerMessage) {
+        if let Some(connection) = self.connections.iter().find(|c| c.id == id) {
+            if Self::is_behind(connection, message) {
+                self.disconnect(id);
+            }
+        }
+    }
+
+    /// Queues `message` for `connection` and returns whether the client fell
+    /// so far behind that there is no room left for it.
+    fn is_behind(connection: &Connection, message: ServerMessage) -> bool {
+        matches!(
+            connection.sender.try_send(message),
+            Err(TrySendError::Full(_))
+        )
+    }
+
+    fn disconnect_all(&mut self, ids: Vec<usize>) {
+        for id in ids {
+            self.disconnect(id);
+        }
+    }
+
+    fn error(message: &str) -> ServerMessage {
+        ServerMessage::Error {
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_server {
+    use std::net::SocketAddr;
+
+    use super::{Connection, Room, OUTBOX_SIZE};
+    use crate::{
+        core::{Board, Direction},
+        net::{Client, ClientMessage, Lobby, Server, ServerMessage},
+    };
+    use tokio::{
+        net::TcpListener,
+        sync::mpsc,
+        time::{timeout, Duration},
+    };
+
+    async fn start_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let boards = vec![
+            Board::new("small".to_string(), 9, Vec::new()),
+            Board::new("big".to_string(), 30, Vec::new()),
+        ];
+
+        tokio::spawn(async move {
+            Server::new(boards, Duration::from_millis(20))
+                .run(listener)
+                .await
+        });
+
+        address
+    }
+
+    async fn receive(client: &mut Client) -> Option<ServerMessage> {
+        timeout(Duration::from_secs(5), client.receive())
+            .await
+            .expect("the server did not answer")
+            .unwrap()
+    }
+
+    /// Skips messages until `pick` accepts one.
+    async fn wait_for<T>(client: &mut Client, pick: impl Fn(ServerMessage) -> Option<T>) -> T {
+        loop {
+            let message = receive(client).await.expect("the server went away");
+            if let Some(found) = pick(message) {
+                return found;
+            }
+        }
+    }
+
+    async fn lobby(client: &mut Client) -> Lobby {
+        wait_for(client, |message| match message {
+            ServerMessage::Lobby(lobby) => Some(lobby),
+            _ => None,
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn lobby_game_and_disconnect() {
+        let address = start_server().await;
+
+        let mut host = Client::join(address, "ali").await.unwrap();
+        let lobby_of_host = lobby(&mut host).await;
+        assert_eq!(lobby_of_host.get_players(), ["ali"]);
+        assert!(lobby_of_host.is_host());
+
+        let mut guest = Client::join(address, " ").await.unwrap();
+        let lobby_of_guest = lobby(&mut guest).await;
+        assert_eq!(lobby_of_guest.get_players(), ["ali", "player 2"]);
+        assert!(!lobby_of_guest.is_host());
+        assert_eq!(lobby(&mut host).await.get_players().len(), 2);
+
+        guest.send(&ClientMessage::Start).await.unwrap();
+        assert!(matches!(
+            receive(&mut guest).await,
+            Some(ServerMessage::Error { .. })
+        ));
+
+        host.send(&ClientMessage::SelectBoard { index: 1 })
+            .await
+            .unwrap();
+        let selected = lobby(&mut guest).await;
+        assert_eq!(selected.get_selected(), 1);
+        assert_eq!(selected.get_board().get_name(), "big");
+
+        host.send(&ClientMessage::Start).await.unwrap();
+        for (client, seat) in [(&mut host, 0), (&mut guest, 1)] {
+            let (board, you) = wait_for(client, |message| match message {
+                ServerMessage::Start { board, you, .. } => Some((board, you)),
+                _ => None,
+            })
+            .await;
+            assert_eq!(board.get_name(), "big");
+            assert_eq!(you, seat);
+        }
+
+        host.send(&ClientMessage::Turn {
+            direction: Direction::Up,
+        })
+        .await
+        .unwrap();
+        wait_for(&mut guest, |message| match message {
+            ServerMessage::Snapshot(snapshot)
+                if snapshot.get_players()[0].get_direction() == Direction::Up =>
+            {
+                Some(())
+            }
+            _ => None,
+        })
+        .await;
+
+        drop(guest);
+        let winner = wait_for(&mut host, |message| match message {
+            ServerMessage::GameOver { winner } => Some(winner),
+            _ => None,
+        })
+        .await;
+        assert_eq!(winner, Some(0));
+        assert_eq!(lobby(&mut host).await.get_players(), ["ali"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_players_that_do_not_fit() {
+        let address = start_server().await;
+
+        let mut players = Vec::new();
+        for name in ["a", "b", "c", "d"] {
+            let mut player = Client::join(address, name).await.unwrap();
+            lobby(&mut player).await;
+            players.push(player);
+        }
+
+        let mut fifth = Client::join(address, "e").await.unwrap();
+        assert!(matches!(
+            receive(&mut fifth).await,
+            Some(ServerMessage::Error { .. })
+        ));
+        assert!(receive(&mut fifth).await.is_none());
+
+        players.truncate(1);
+        players[0].send(&ClientMessage::Start).await.unwrap();
+        wait_for(&mut players[0], |message| match message {
+            ServerMessage::Snapshot(_) => Some(()),
+            _ => None,
+        })
+        .await;
+
+        let mut late = Client::join(address, "late").await.unwrap();
+        assert!(matches!(
+            receive(&mut late).await,
+            Some(ServerMessage::Error { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn refuses_to_serve_without_boards() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server = Server::new(Vec::new(), Duration::from_millis(20));
+
+        assert!(server.run(listener).await.is_err());
+    }
+
+    #[test]
+    fn disconnects_clients_that_fall_behind() {
+        let boards = vec![Board::new("small".to_string(), 9, Vec::new())];
+        let mut room = Room::new(&boards);
+        let (sender, _outbox) = mpsc::channel(OUTBOX_SIZE);
+        room.connections.push(Connection {
+            id: 0,
+            name: Some("slow".to_string()),
+            sender,
+        });
+
+        for _ in 0..OUTBOX_SIZE {
+            room.broadcast_lobby();
+        }
+        assert_eq!(room.players().len(), 1);
+        room.broadcast_lobby();
+        assert!(room.connections.is_empty());
+    }
+}
@@ -1,37 +1,67 @@
+mod campaign;
 mod create_board;
 mod game;
 mod game_over;
+mod lineup;
+mod online;
+mod popup;
+mod render_mode;
+mod replay;
+mod save;
 mod scoreboard;
 mod select_board;
+mod settings;
+mod viewport;
+mod winner;
 
-use crate::core::{Board, Game};
+use crate::{
+    core::{Board, Boards, Campaign, Death, Game, Level, Player, Progress, Ranking, Replay},
+    net::Client,
+};
 
 use std::io;
 
+use campaign::{CampaignTui, CampaignTuiResult};
 use create_board::CreateBoardTui;
-use game::GameTui;
-use game_over::GameOverTui;
+use game::{GameTui, GameTuiResult};
+use game_over::{GameOverTui, GameOverTuiResult};
+use lineup::Lineup;
+use online::OnlineTui;
 use ratatui::DefaultTerminal;
+use save::Save;
 use scoreboard::ScoreboardTui;
 use select_board::{SelectBoardTui, SelectBoardTuiResult};
+use settings::Settings;
+use winner::{WinnerTui, WinnerTuiResult};
 
 enum State {
-    SelectBoard,
+    /// The board list, with a word on why the saved game could not go on.
+    SelectBoard(Option<String>),
     CreateBoard,
-    PlayGame(Board),
-    GameOver(u16, String),
+    PlayGame(Board, Lineup),
+    GameOver(Board, u16, Replay, Option<Death>, bool, Ranking),
+    Winner(Board, Lineup, Vec<Player>, Option<usize>, Replay),
     Scoreboard,
+    /// The campaign map, with a word on how the last level went.
+    Campaign(Option<String>),
+    PlayLevel(usize, Level),
+    /// A saved game and the board it goes on on.
+    Continue(Board, Box<Save>),
 }
 
 struct App {
     state: State,
+    settings: Settings,
+    player: String,
     exit: bool,
 }
 
 impl App {
     pub fn new() -> Self {
         Self {
-            state: State::SelectBoard,
+            state: State::SelectBoard(None),
+            settings: Settings::default(),
+            player: String::new(),
             exit: false,
         }
     }
@@ -39,39 +69,150 @@ impl App {
     pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
         while !self.exit {
             self.state = match &self.state {
-                State::SelectBoard => {
-                    let mut select_board_tui = SelectBoardTui::new();
+                State::SelectBoard(message) => {
+                    let mut select_board_tui = SelectBoardTui::new(message.clone());
 
                     match select_board_tui.run(terminal)? {
-                        SelectBoardTuiResult::Board(board) => State::PlayGame(board),
+                        SelectBoardTuiResult::Board(board, lineup) => {
+                            State::PlayGame(board, lineup)
+                        }
+                        SelectBoardTuiResult::Continue(board, save) => State::Continue(board, save),
                         SelectBoardTuiResult::Exit => {
                             self.exit = true;
-                            State::SelectBoard
+                            State::SelectBoard(None)
                         }
                         SelectBoardTuiResult::CreateBoard => State::CreateBoard,
                         SelectBoardTuiResult::ScoreBoards => State::Scoreboard,
+                        SelectBoardTuiResult::Campaign => State::Campaign(None),
                     }
                 }
                 State::CreateBoard => {
                     let mut create_board_tui = CreateBoardTui::new();
                     create_board_tui.run(terminal)?;
-                    State::SelectBoard
+                    State::SelectBoard(None)
+                }
+                State::PlayGame(board, lineup) => {
+                    let game = Game::with_mode(
+                        board,
+                        3,
+                        rand::random(),
+                        lineup.get_players(),
+                        lineup.get_mode(),
+                    );
+                    match GameTui::new(game, *lineup, self.settings) {
+                        Ok(mut game_tui) => {
+                            let result = game_tui.run(terminal).await?;
+                            self.settings = game_tui.get_settings();
+
+                            Self::after_game(result, board, *lineup)?
+                        }
+                        Err(error) => State::SelectBoard(Some(error.to_string())),
+                    }
                 }
-                State::PlayGame(board) => {
-                    let mut game_tui = GameTui::new(Game::new(board, 3));
-                    let score = game_tui.run(terminal).await?;
-                    State::GameOver(score, board.get_name().to_string())
+                State::GameOver(board, score, replay, death, cleared, ranking) => {
+                    let mut game_over_tui = GameOverTui::new(
+                        board.clone(),
+                        *score,
+                        replay.clone(),
+                        death.clone(),
+                        *ranking,
+                        self.player.clone(),
+                        self.settings,
+                    )
+                    .cleared(*cleared);
+                    let result = game_over_tui.run(terminal).await?;
+                    self.player = game_over_tui.get_player().to_string();
+
+                    match result {
+                        GameOverTuiResult::Retry => {
+                            let mut lineup = Lineup::solo(replay.get_mode());
+                            if *ranking == Ranking::Practice {
+                                lineup.toggle_practice();
+                            }
+                            State::PlayGame(board.clone(), lineup)
+                        }
+                        GameOverTuiResult::Scoreboard => State::Scoreboard,
+                        GameOverTuiResult::Menu => State::SelectBoard(None),
+                    }
                 }
-                State::GameOver(score, board_name) => {
-                    let game_over_tui = GameOverTui::new(board_name.to_owned(), *score);
-                    game_over_tui.run(terminal).await?;
+                State::Winner(board, lineup, players, winner, replay) => {
+                    let mut winner_tui = WinnerTui::new(
+                        board.clone(),
+                        *lineup,
+                        players.clone(),
+                        *winner,
+                        replay.clone(),
+                        self.settings,
+                    );
 
-                    State::SelectBoard
+                    match winner_tui.run(terminal).await? {
+                        WinnerTuiResult::Rematch => State::PlayGame(board.clone(), *lineup),
+                        WinnerTuiResult::Menu => State::SelectBoard(None),
+                    }
                 }
                 State::Scoreboard => {
                     let mut scoreboard = ScoreboardTui::new();
                     scoreboard.run(terminal)?;
-                    State::SelectBoard
+                    State::SelectBoard(None)
+                }
+                State::Campaign(message) => {
+                    let mut campaign_tui = CampaignTui::new(self.player.clone(), message.clone());
+                    let result = campaign_tui.run(terminal)?;
+                    self.player = campaign_tui.get_player().to_string();
+
+                    match result {
+                        CampaignTuiResult::Play(index, level) => State::PlayLevel(index, level),
+                        CampaignTuiResult::Menu => State::SelectBoard(None),
+                    }
+                }
+                State::PlayLevel(index, level) => {
+                    let game = Game::new(level.get_board(), 3);
+                    let mut game_tui = GameTui::new(game, Lineup::default(), self.settings)?
+                        .with_target(level.get_target());
+                    let result = game_tui.run(terminal).await?;
+                    self.settings = game_tui.get_settings();
+
+                    Self::after_level(result, *index, level, &self.player)?
+                }
+                State::Continue(board, save) => {
+                    let resumed = Game::resume(board, save.get_game().clone()).and_then(|game| {
+                        GameTui::new(game, save.get_lineup(), self.settings)
+                            .map_err(|error| error.to_string())
+                    });
+
+                    match resumed {
+                        Ok(game_tui) => {
+                            // a saved game is only continued once
+                            Save::remove();
+
+                            // a level whose board moved since is played as a plain game
+                            let level = save.get_level().and_then(|index| {
+                                let campaign = Campaign::from_boards(Boards::new().get_all());
+                                campaign
+                                    .get_levels()
+                                    .get(index)
+                                    .filter(|level| {
+                                        level.get_board().get_name() == board.get_name()
+                                    })
+                                    .cloned()
+                            });
+                            let mut game_tui =
+                                game_tui.resumed(save.get_elapsed(), save.is_assisted());
+                            if let Some(level) = &level {
+                                game_tui = game_tui.with_target(level.get_target());
+                            }
+                            let result = game_tui.run(terminal).await?;
+                            self.settings = game_tui.get_settings();
+
+                            match (save.get_level(), level) {
+                                (Some(index), Some(level)) => {
+                                    Self::after_level(result, index, &level, &self.player)?
+                                }
+                                _ => Self::after_game(result, board, save.get_lineup())?,
+                            }
+                        }
+                        Err(error) => State::SelectBoard(Some(error)),
+                    }
                 }
             };
         }
@@ -80,6 +221,54 @@ impl App {
 
         Ok(())
     }
+
+    /// Where a game on `board` goes once it ends.
+    fn after_game(result: GameTuiResult, board: &Board, lineup: Lineup) -> io::Result<State> {
+        let state = match result {
+            GameTuiResult::GameOver(score, replay, death, cleared, ranking) => {
+                State::GameOver(board.clone(), score, replay, death, cleared, ranking)
+            }
+            GameTuiResult::Winner(players, winner, replay) => {
+                State::Winner(board.clone(), lineup, players, winner, replay)
+            }
+            GameTuiResult::Saved(game, elapsed, assisted) => {
+                Save::new(*game, lineup, elapsed, assisted, None).write()?;
+                State::SelectBoard(None)
+            }
+            GameTuiResult::TargetMet(_) | GameTuiResult::Menu => State::SelectBoard(None),
+        };
+
+        Ok(state)
+    }
+
+    /// Where the `index`-th campaign level, `level`, goes once it ends,
+    /// recording it for `player` when it was won.
+    fn after_level(
+        result: GameTuiResult,
+        index: usize,
+        level: &Level,
+        player: &str,
+    ) -> io::Result<State> {
+        let message = match result {
+            GameTuiResult::TargetMet(false) => {
+                Progress::new().complete(player, level);
+                format!("Level {} complete!", index + 1)
+            }
+            GameTuiResult::TargetMet(true) => {
+                "The autopilot played, so the level does not count.".to_string()
+            }
+            GameTuiResult::GameOver(_, _, Some(death), _, _) => {
+                format!("Level {} failed. {}", index + 1, death.describe())
+            }
+            GameTuiResult::Saved(game, elapsed, assisted) => {
+                Save::new(*game, Lineup::default(), elapsed, assisted, Some(index)).write()?;
+                return Ok(State::SelectBoard(None));
+            }
+            _ => format!("Level {} is not complete yet.", index + 1),
+        };
+
+        Ok(State::Campaign(Some(message)))
+    }
 }
 
 pub struct Tui {}
@@ -91,4 +280,14 @@ impl Tui {
         ratatui::restore();
         app_result
     }
+
+    /// Plays on a server through `client` instead of locally.
+    pub async fn join(client: Client) -> Result<(), std::io::Error> {
+        let mut terminal = ratatui::init();
+        let app_result = OnlineTui::new(client, Settings::default())
+            .run(&mut terminal)
+            .await;
+        ratatui::restore();
+        app_result
+    }
 }
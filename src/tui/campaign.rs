@@ -0,0 +1,266 @@
+use super::{
+    render_mode::{self, RenderMode},
+    viewport::Viewport,
+};
+use crate::core::{Boards, Campaign, Level, Progress};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    symbols::border,
+    text::{Line, Text},
+    widgets::{
+        Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
+        Widget,
+    },
+    DefaultTerminal, Frame,
+};
+use std::io;
+
+const MAX_NAME_LENGTH: usize = 12;
+
+pub enum CampaignTuiResult {
+    /// The index of the chosen level and the level.
+    Play(usize, Level),
+    Menu,
+}
+
+/// The campaign map: every level in order, what it asks for and which ones
+/// the player unlocked.
+pub struct CampaignTui {
+    campaign: Campaign,
+    progress: Progress,
+    player: String,
+    naming: bool,
+    message: Option<String>,
+    state: ListState,
+    viewport: Viewport,
+    result: Option<CampaignTuiResult>,
+}
+
+impl CampaignTui {
+    /// The map for `player`, showing `message` about the last level played.
+    pub fn new(player: String, message: Option<String>) -> Self {
+        let campaign = Campaign::from_boards(Boards::new().get_all());
+        let progress = Progress::new();
+
+        let mut state = ListState::default();
+        let completed = progress.get_completed(&player, &campaign);
+        state.select(Some(
+            completed.min(campaign.get_levels().len().saturating_sub(1)),
+        ));
+
+        Self {
+            campaign,
+            progress,
+            player,
+            naming: false,
+            message,
+            state,
+            viewport: Viewport::centered(),
+            result: None,
+        }
+    }
+
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<CampaignTuiResult> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Some(result) = self.result.take() {
+                return Ok(result);
+            }
+
+            match event::read()? {
+                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                    match self.naming {
+                        true => self.key_event_name(key_event),
+                        false => self.key_event(key_event),
+                    }
+                }
+                _ => {}
+            };
+        }
+    }
+
+    /// The name the progress is kept under, which may have changed here.
+    pub fn get_player(&self) -> &str {
+        &self.player
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+    }
+
+    fn key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
+                self.result = Some(CampaignTuiResult::Menu)
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.naming = true;
+                self.player.clear();
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.state.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.state.select_previous(),
+            KeyCode::Enter => {
+                let Some(index) = self.state.selected() else {
+                    return;
+                };
+                let Some(level) = self.campaign.get_levels().get(index) else {
+                    return;
+                };
+                if self.campaign.is_unlocked(index, self.completed())
+                    || self.progress.is_completed(&self.player, level)
+                {
+                    self.result = Some(CampaignTuiResult::Play(index, level.clone()));
+                } else {
+                    self.message = Some("Complete the levels before this one first.".to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn key_event_name(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char(c)
+                if (c.is_alphanumeric() || c == ' ') && self.player.len() < MAX_NAME_LENGTH =>
+            {
+                self.player.push(c)
+            }
+            KeyCode::Backspace => {
+                self.player.pop();
+            }
+            KeyCode::Enter => {
+                self.player = self.player.trim().to_string();
+                self.naming = false;
+                self.message = None;
+                let completed = self.completed();
+                let last = self.campaign.get_levels().len().saturating_sub(1);
+                self.state.select(Some(completed.min(last)));
+            }
+            _ => {}
+        }
+    }
+
+    fn completed(&self) -> usize {
+        self.progress.get_completed(&self.player, &self.campaign)
+    }
+
+    fn render_header(&self, area: Rect, buf: &mut Buffer) {
+        let status = match (&self.message, self.naming) {
+            (_, true) => Line::from(format!("Your name: {}_", self.player)),
+            (Some(message), false) => Line::from(message.as_str()).yellow(),
+            (None, false) => Line::from(format!(
+                "Playing as {}, {} of {} levels completed.",
+                match self.player.is_empty() {
+                    true => "anonymous",
+                    false => &self.player,
+                },
+                self.campaign
+                    .get_levels()
+                    .iter()
+                    .filter(|level| self.progress.is_completed(&self.player, level))
+                    .count(),
+                self.campaign.get_levels().len()
+            )),
+        };
+
+        Paragraph::new(Text::from(vec![Line::from("Campaign").bold(), status]))
+            .centered()
+            .render(area, buf);
+    }
+
+    fn render_footer(&self, area: Rect, buf: &mut Buffer) {
+        let instructions = match self.naming {
+            true => "Type your name, ⮡ to save.",
+            false => "Use ↓↑ to move, ⮡ to play the selected level, n/N to change player, q/Q to go back.",
+        };
+
+        Paragraph::new(instructions).centered().render(area, buf);
+    }
+
+    fn render_levels(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(Line::raw(" Levels ").centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+
+        let completed = self.completed();
+        let items: Vec<_> = self
+            .campaign
+            .get_levels()
+            .iter()
+            .enumerate()
+            .map(|(index, level)| {
+                let text = format!("{}. {}", index + 1, level.get_board().get_name());
+                if self.progress.is_completed(&self.player, level) {
+                    ListItem::from(format!("✓ {}", text)).green()
+                } else if self.campaign.is_unlocked(index, completed) {
+                    ListItem::from(format!("  {}", text))
+                } else {
+                    ListItem::from(format!("  {}", text)).dark_gray()
+                }
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.state);
+    }
+
+    fn render_selected_level(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(level) = self
+            .state
+            .selected()
+            .and_then(|index| self.campaign.get_levels().get(index))
+        else {
+            return;
+        };
+        let table = level.get_board().get_table();
+        let center = table.len() / 2;
+
+        let block = Block::new()
+            .title(Line::raw(format!(" {} ", level.get_board().get_name())).centered())
+            .title_bottom(Line::raw(format!(" {} ", level.get_target().describe())).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+        let board_area = block.inner(area);
+        block.render(area, buf);
+
+        self.viewport.render(
+            &table,
+            &render_mode::unstyled(&table),
+            (center, center),
+            RenderMode::default(),
+            board_area,
+            buf,
+        );
+    }
+}
+
+impl Widget for &mut CampaignTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [header_area, main_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let [list_area, level_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+                .areas(main_area);
+
+        self.render_header(header_area, buf);
+        self.render_footer(footer_area, buf);
+        self.render_levels(list_area, buf);
+        self.render_selected_level(level_area, buf);
+    }
+}
@@ -1,18 +1,38 @@
 use std::io;
 
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use itertools::Itertools;
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Rect},
-    style::Stylize,
+    layout::Rect,
+    style::{Style, Stylize},
     symbols::border,
-    text::{Line, Text},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Paragraph, Widget},
     DefaultTerminal, Frame,
 };
 
-use crate::core::{Board, Boards, Direction, Wall};
+use super::{
+    game::tile_style,
+    render_mode::{self, RenderMode},
+    viewport::Viewport,
+};
+use crate::core::{Board, Boards, Direction, Entity, Motion, Tile, Wall, PORTAL_PAIRS};
+
+const VIEWPORT_MARGIN: usize = 2;
+/// The tiles a board can be drawn with, in the order t/T goes through them.
+/// Portals get their pair when they are put down.
+const PALETTE: [Tile; 10] = [
+    Tile::Wall,
+    Tile::Breakable,
+    Tile::Mud,
+    Tile::Ice,
+    Tile::SpeedPad,
+    Tile::OneWay(Direction::Up),
+    Tile::OneWay(Direction::Right),
+    Tile::OneWay(Direction::Down),
+    Tile::OneWay(Direction::Left),
+    Tile::Portal(0),
+];
 
 enum State {
     Size,
@@ -26,9 +46,16 @@ pub struct CreateBoardTui {
     exit: bool,
     finish: bool,
     wall: Wall,
+    /// The tile of the palette space puts down.
+    tile: usize,
+    /// The way the cursor last moved, which new bouncers head.
+    heading: Direction,
+    /// The cells of the patrol being laid out, as the cursor goes over them.
+    path: Option<Vec<Wall>>,
     state: State,
     boards: Boards,
     error: String,
+    viewport: Viewport,
 }
 
 impl Default for CreateBoardTui {
@@ -46,9 +73,13 @@ impl CreateBoardTui {
             exit: false,
             finish: false,
             wall: Wall::new(0, 0),
+            tile: 0,
+            heading: Direction::Right,
+            path: None,
             state: State::Size,
             boards: Boards::new(),
             error: "".to_string(),
+            viewport: Viewport::new(VIEWPORT_MARGIN),
         }
     }
 
@@ -69,11 +100,21 @@ impl CreateBoardTui {
     fn key_event_put_wall(&mut self, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Char('q') => self.exit = true,
-            KeyCode::Char('j') | KeyCode::Down => self.select_down(),
-            KeyCode::Char('k') | KeyCode::Up => self.select_up(),
-            KeyCode::Char('h') | KeyCode::Left => self.select_left(),
-            KeyCode::Char('l') | KeyCode::Right => self.select_right(),
-            KeyCode::Char(' ') => self.toggle_wall(),
+            KeyCode::Char('j') | KeyCode::Down => self.select(Direction::Down),
+            KeyCode::Char('k') | KeyCode::Up => self.select(Direction::Up),
+            KeyCode::Char('h') | KeyCode::Left => self.select(Direction::Left),
+            KeyCode::Char('l') | KeyCode::Right => self.select(Direction::Right),
+            KeyCode::Char(' ') => self.toggle_tile(),
+            KeyCode::Char('p') => self.toggle_patrol(),
+            KeyCode::Char('b') => self
+                .board
+                .add_entity(Entity::bounce(self.wall.clone(), self.heading)),
+            KeyCode::Char('c') => self.board.add_entity(Entity::chase(self.wall.clone())),
+            KeyCode::Char('x') => self.board.remove_entities(&self.wall),
+            KeyCode::Char('t') | KeyCode::Tab => self.tile = (self.tile + 1) % PALETTE.len(),
+            KeyCode::Char('T') | KeyCode::BackTab => {
+                self.tile = (self.tile + PALETTE.len() - 1) % PALETTE.len()
+            }
             KeyCode::Enter => self.state = State::Name,
             _ => {}
         }
@@ -118,29 +159,54 @@ impl CreateBoardTui {
         Ok(())
     }
 
-    fn select_down(&mut self) {
-        self.wall = self.wall.get_neighbor(&Direction::Down, self.size);
-    }
-
-    fn select_up(&mut self) {
-        self.wall = self.wall.get_neighbor(&Direction::Up, self.size);
-    }
+    /// Moves the cursor, adding the cell to the patrol being laid out; going
+    /// back to the cell before takes the last one away again.
+    fn select(&mut self, direction: Direction) {
+        self.wall = self.wall.get_neighbor(&direction, self.size);
+        self.heading = direction;
 
-    fn select_left(&mut self) {
-        self.wall = self.wall.get_neighbor(&Direction::Left, self.size);
+        if let Some(path) = &mut self.path {
+            match path.len() >= 2 && path[path.len() - 2] == self.wall {
+                true => {
+                    path.pop();
+                }
+                false => path.push(self.wall.clone()),
+            }
+        }
     }
 
-    fn select_right(&mut self) {
-        self.wall = self.wall.get_neighbor(&Direction::Right, self.size);
+    /// Starts laying out a patrol at the cursor, or finishes the one being
+    /// laid out.
+    fn toggle_patrol(&mut self) {
+        match self.path.take() {
+            Some(path) if path.len() >= 2 => {
+                if let Some(patrol) = Entity::patrol(path) {
+                    self.board.add_entity(patrol);
+                }
+            }
+            Some(_) => {}
+            None => self.path = Some(Vec::from([self.wall.clone()])),
+        }
     }
 
-    fn toggle_wall(&mut self) {
-        let wall = &self.wall;
-        if self.board.is_wall(wall) {
-            self.board.remove_wall(wall);
-        } else {
-            self.board.add_wall(wall.clone()).unwrap();
-        }
+    /// Puts the selected tile of the palette under the cursor, or clears
+    /// the cell when that tile is already there. A portal joins the first
+    /// pair still missing an end.
+    fn toggle_tile(&mut self) {
+        let tile = match (self.board.get_tile(&self.wall), PALETTE[self.tile]) {
+            (Tile::Portal(_), Tile::Portal(_)) => Tile::Floor,
+            (_, Tile::Portal(_)) => match self.board.next_portal_pair() {
+                Some(pair) => Tile::Portal(pair),
+                None => {
+                    self.error = format!("A board has {} pairs of portals at most.", PORTAL_PAIRS);
+                    return;
+                }
+            },
+            (current, selected) if current == selected => Tile::Floor,
+            (_, selected) => selected,
+        };
+        self.error.clear();
+        self.board.set_tile(self.wall.clone(), tile).unwrap();
     }
 
     fn store(&mut self) {
@@ -153,27 +219,88 @@ impl CreateBoardTui {
 }
 
 impl CreateBoardTui {
-    fn render_put_wall(&self, area: Rect, buf: &mut Buffer) {
+    fn render_put_wall(&mut self, area: Rect, buf: &mut Buffer) {
+        let cursor = (
+            (self.wall.get_x() + 1) as usize,
+            (self.wall.get_y() + 1) as usize,
+        );
+
+        let cell = |point: &Wall| ((point.get_x() + 1) as usize, (point.get_y() + 1) as usize);
+
         let mut selected_board = self.board.get_table();
-        selected_board[(self.wall.get_x() + 1) as usize][(self.wall.get_y() + 1) as usize] =
-            if selected_board[(self.wall.get_x() + 1) as usize][(self.wall.get_y() + 1) as usize]
-                == "  "
-            {
-                "■■".to_string()
-            } else {
-                "▀▀".to_string()
-            };
-        let selected_board = selected_board.iter().map(|row| row.join("")).join("\n");
+        let mut styles = render_mode::unstyled(&selected_board);
+        for placed in self.board.get_tiles() {
+            let (row, column) = cell(placed.get_point());
+            styles[row][column] = tile_style(placed.get_tile());
+        }
+
+        let patrols = self
+            .board
+            .get_entities()
+            .iter()
+            .filter_map(|entity| match entity.get_motion() {
+                Motion::Patrol(path) => Some((path, Style::new().dark_gray())),
+                _ => None,
+            })
+            .chain(self.path.iter().map(|path| (path, Style::new().yellow())));
+        for (path, style) in patrols {
+            for (row, column) in path.iter().map(cell) {
+                selected_board[row][column] = " ·".to_string();
+                styles[row][column] = style;
+            }
+        }
+        for entity in self.board.get_entities() {
+            let (row, column) = cell(entity.get_point());
+            selected_board[row][column] = entity.get_glyph().to_string();
+            styles[row][column] = Style::new().red();
+        }
+
+        selected_board[cursor.0][cursor.1] = if selected_board[cursor.0][cursor.1] == "  " {
+            "■■".to_string()
+        } else {
+            "▀▀".to_string()
+        };
+
+        let tile = match PALETTE[self.tile] {
+            Tile::Portal(_) => Tile::Portal(self.board.next_portal_pair().unwrap_or(0)),
+            tile => tile,
+        };
+        let mut palette = Line::from(vec![
+            Span::raw(" Tile: "),
+            Span::styled(tile.get_glyph(), tile_style(tile)),
+            Span::raw(format!(" {} ", tile.get_name())),
+        ]);
+        if !self.error.is_empty() {
+            palette.push_span(Span::from(format!("{} ", self.error)).red());
+        }
+        let instructions = Line::from(
+            " Use 🠀 🠂 🠁 🠃 or h j k l to move, space to put the tile, t/T to change it, ⮡ to name the board, q to quit. ",
+        );
+        let entities = match self.path {
+            Some(_) => Line::from(" Move to lay out the patrol, p to finish it. ").yellow(),
+            None => Line::from(
+                " p for a patrol, b for a bouncer heading the last move, c for a chaser, x to remove. ",
+            ),
+        };
 
         let block = Block::new()
             .title(Line::raw(" Selected Board ").centered())
+            .title(palette.left_aligned())
+            .title_bottom(entities.left_aligned())
+            .title_bottom(instructions.centered())
             .borders(Borders::ALL)
             .border_set(border::ROUNDED);
+        let board_area = block.inner(area);
+        block.render(area, buf);
 
-        Paragraph::new(selected_board)
-            .block(block)
-            .alignment(Alignment::Center)
-            .render(area, buf);
+        self.viewport.render(
+            &selected_board,
+            &styles,
+            cursor,
+            RenderMode::Wide,
+            board_area,
+            buf,
+        );
     }
 
     fn render_put_size(&self, area: Rect, buf: &mut Buffer) {
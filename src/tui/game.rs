@@ -1,56 +1,212 @@
-use crate::core::{Direction, Game};
+use crate::core::{
+    Bot, Controller, Death, Difficulty, Direction, FoodKind, Game, GameMode, Grid, Player, Point,
+    Ranking, Replay, Rewind, SavedGame, Target, Tile,
+};
+
+use super::{
+    lineup::Lineup,
+    popup::popup_area,
+    render_mode::{self, RenderMode},
+    settings::Settings,
+    viewport::Viewport,
+};
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use itertools::Itertools;
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind};
+use futures::StreamExt;
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    text::Line,
-    widgets::{Block, Paragraph, Widget},
+    layout::{Rect, Size},
+    style::{Color, Modifier, Style, Stylize},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Clear, HighlightSpacing, List, ListItem, ListState, Paragraph,
+        StatefulWidget, Widget, Wrap,
+    },
     DefaultTerminal, Frame,
 };
-use std::{io, time};
-use tokio::time::{sleep, Duration};
+use std::io;
+use tokio::time::{interval, interval_at, Duration, Instant, Interval, MissedTickBehavior};
+
+const TITLE_HEIGHT: u16 = 2;
+const FRAME: Duration = Duration::from_millis(16);
+const CRASH_ANIMATION: Duration = Duration::from_millis(1600);
+const BLINK: Duration = Duration::from_millis(200);
+/// Walks a practice game can be rewound by, about nine seconds at the
+/// default speed.
+const REWIND_TICKS: usize = 100;
+/// How long the game holds still after a rewind, so that holding the key
+/// rewinds on and letting go of it leaves time to take over.
+const REWIND_PAUSE: Duration = Duration::from_millis(600);
+const PAUSE_ITEMS: [&str; 6] = [
+    "Resume",
+    "Restart (same seed)",
+    "Restart (new seed)",
+    "Settings",
+    "Save and quit",
+    "Quit to menu",
+];
+const SETTINGS_ITEMS: usize = 5;
+const PLAYER_COLORS: [Color; 4] = [Color::Green, Color::Cyan, Color::Magenta, Color::Yellow];
+
+pub enum GameTuiResult {
+    /// The score, how the game went, why it ended, whether the board was
+    /// cleared and where the score goes.
+    GameOver(u16, Replay, Option<Death>, bool, Ranking),
+    Winner(Vec<Player>, Option<usize>, Replay),
+    /// The campaign target was met, and whether the autopilot played.
+    TargetMet(bool),
+    /// The game to continue later, how long it was played and whether the
+    /// autopilot played.
+    Saved(Box<SavedGame>, Duration, bool),
+    Menu,
+}
+
+enum Pause {
+    Menu,
+    Settings,
+}
 
 pub struct GameTui<'a> {
     game: Game<'a>,
-    stop: bool,
+    lineup: Lineup,
+    bots: Vec<(usize, Bot)>,
+    autopilot: Option<Bot>,
+    assisted: bool,
+    settings: Settings,
+    viewport: Viewport,
+    terminal_size: Size,
+    elapsed: Duration,
+    /// What ends a campaign level early, once it is met.
+    target: Option<Target>,
+    /// The states a practice game can go back to.
+    rewind: Option<Rewind<'a>>,
+    rewound_at: Option<Instant>,
+    crashed_at: Option<Instant>,
+    pause: Option<Pause>,
+    pause_menu: ListState,
+    settings_menu: ListState,
+    quit_to_menu: bool,
+    save_and_quit: bool,
     exit: bool,
 }
 
 impl<'a> GameTui<'a> {
-    pub fn new(game: Game<'a>) -> Self {
-        Self {
+    /// Fails when a bot of `lineup` cannot be made.
+    pub fn new(game: Game<'a>, lineup: Lineup, settings: Settings) -> io::Result<Self> {
+        let mut pause_menu = ListState::default();
+        pause_menu.select_first();
+
+        let mut settings_menu = ListState::default();
+        settings_menu.select_first();
+
+        Ok(Self {
+            bots: lineup.bots(game.get_seed())?,
             game,
-            stop: false,
+            lineup,
+            autopilot: None,
+            assisted: false,
+            settings,
+            viewport: Viewport::new(settings.get_viewport_margin()),
+            terminal_size: Size::default(),
+            elapsed: Duration::ZERO,
+            target: None,
+            rewind: lineup.is_practice().then(|| Rewind::new(REWIND_TICKS)),
+            rewound_at: None,
+            crashed_at: None,
+            pause: None,
+            pause_menu,
+            settings_menu,
+            quit_to_menu: false,
+            save_and_quit: false,
             exit: false,
-        }
+        })
     }
 
-    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<u16> {
-        while !self.exit {
-            terminal.draw(|frame| self.draw(frame))?;
-            self.exit = !self.game.walk();
+    /// Plays a campaign level, which is won as soon as `target` is met.
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = Some(target);
+        self
+    }
 
-            if event::poll(time::Duration::from_millis(10))? {
-                self.handle_events()?;
-            }
+    /// Goes on with a saved game that was played for `elapsed` already.
+    pub fn resumed(mut self, elapsed: Duration, assisted: bool) -> Self {
+        self.elapsed = elapsed;
+        self.assisted = assisted;
+        self
+    }
+
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<GameTuiResult> {
+        self.terminal_size = terminal.size()?;
 
-            sleep(Duration::from_millis(80)).await;
+        let mut events = EventStream::new();
+        let mut tick = Self::ticker(self.settings.get_tick());
+        let mut frame = interval(FRAME);
+        frame.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
-            while self.stop && !self.exit {
-                self.handle_events()?;
+        while !self.exit {
+            tokio::select! {
+                _ = tick.tick() => self.on_tick(tick.period()),
+                _ = frame.tick() => {
+                    terminal.draw(|frame| self.draw(frame))?;
+                }
+                Some(event) = events.next() => self.handle_event(event?),
+            }
+
+            if tick.period() != self.settings.get_tick() {
+                tick = Self::ticker(self.settings.get_tick());
             }
         }
 
-        Ok(self.game.get_score())
+        if self.quit_to_menu {
+            Ok(GameTuiResult::Menu)
+        } else if self.save_and_quit {
+            Ok(GameTuiResult::Saved(
+                Box::new(self.game.save()),
+                self.elapsed,
+                self.assisted,
+            ))
+        } else if self.is_target_met() {
+            Ok(GameTuiResult::TargetMet(self.assisted))
+        } else if self.is_multiplayer() {
+            Ok(GameTuiResult::Winner(
+                self.game.get_players().to_vec(),
+                self.game.get_winner(),
+                self.game.get_replay().clone(),
+            ))
+        } else {
+            Ok(GameTuiResult::GameOver(
+                self.game.get_score(),
+                self.game.get_replay().clone(),
+                self.game.get_death().cloned(),
+                self.game.is_cleared(),
+                self.get_ranking(),
+            ))
+        }
     }
 
-    fn draw(&self, frame: &mut Frame) {
+    pub fn get_settings(&self) -> Settings {
+        self.settings
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
     }
 
+    fn is_multiplayer(&self) -> bool {
+        self.game.get_players().len() > 1
+    }
+
     fn key_event_play_mode(&mut self, key_event: KeyEvent) {
+        if self.lineup.get_humans() > 1 {
+            if let Some((player, direction)) = player_key(key_event.code) {
+                if !self.lineup.is_bot(player) {
+                    self.game.rotate(player, direction);
+                }
+                return;
+            }
+        }
+
         match key_event.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => self.exit = true,
             KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('k') => {
@@ -65,49 +221,637 @@ impl<'a> GameTui<'a> {
             KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('l') => {
                 self.game.rotation(Direction::Right)
             }
-            KeyCode::Esc => self.stop = true,
+            KeyCode::Char('v') | KeyCode::Char('V') => self.settings.next_render_mode(),
+            KeyCode::Char('g') | KeyCode::Char('G') => self.settings.toggle_path_hint(),
+            KeyCode::Char('o') | KeyCode::Char('O') => self.toggle_autopilot(),
+            KeyCode::Char('r') | KeyCode::Char('R') => self.rewind(),
+            KeyCode::Esc => self.pause = Some(Pause::Menu),
             _ => {}
         }
     }
-    fn key_event_stop_mode(&mut self, key_event: KeyEvent) {
+
+    /// Lets a hard bot steer the first snake, or takes it back.
+    ///
+    /// A game the autopilot played in does not count for the scoreboard.
+    fn toggle_autopilot(&mut self) {
+        self.autopilot = match self.autopilot {
+            Some(_) => None,
+            None => {
+                self.assisted = true;
+                let bot = Difficulty::Hard.bot(self.game.get_seed());
+                Some(bot.expect("only a trained bot needs a genome"))
+            }
+        };
+    }
+
+    fn key_event_pause_menu(&mut self, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => self.exit = true,
-            KeyCode::Esc => self.stop = false,
+            KeyCode::Esc => self.pause = None,
+            KeyCode::Char('j') | KeyCode::Down => self.pause_menu.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.pause_menu.select_previous(),
+            KeyCode::Enter => match self.pause_menu.selected() {
+                Some(0) => self.pause = None,
+                Some(1) => self.restart(self.game.get_seed()),
+                Some(2) => self.restart(rand::random()),
+                Some(3) => self.pause = Some(Pause::Settings),
+                Some(4) => {
+                    self.save_and_quit = true;
+                    self.exit = true;
+                }
+                Some(5) => {
+                    self.quit_to_menu = true;
+                    self.exit = true;
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn key_event_settings(&mut self, key_event: KeyEvent) {
+        let selected = self.settings_menu.selected();
+
+        match key_event.code {
+            KeyCode::Esc => self.pause = Some(Pause::Menu),
+            KeyCode::Enter if selected == Some(SETTINGS_ITEMS - 1) => {
+                self.pause = Some(Pause::Menu)
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.settings_menu.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.settings_menu.select_previous(),
+            KeyCode::Char('h') | KeyCode::Left => match selected {
+                Some(0) => self.settings.faster(),
+                Some(1) => self.settings.next_render_mode(),
+                Some(2) => self.settings.narrower_margin(),
+                Some(3) => self.settings.toggle_path_hint(),
+                _ => {}
+            },
+            KeyCode::Char('l') | KeyCode::Right => match selected {
+                Some(0) => self.settings.slower(),
+                Some(1) => self.settings.next_render_mode(),
+                Some(2) => self.settings.wider_margin(),
+                Some(3) => self.settings.toggle_path_hint(),
+                _ => {}
+            },
             _ => {}
         }
+
+        self.viewport
+            .set_margin(self.settings.get_viewport_margin());
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                if !self.stop {
-                    self.key_event_play_mode(key_event)
-                } else {
-                    self.key_event_stop_mode(key_event)
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Key(key_event)
+                if key_event.kind == KeyEventKind::Press && self.crashed_at.is_some() =>
+            {
+                match (key_event.code, &self.rewind) {
+                    (KeyCode::Char('r') | KeyCode::Char('R'), Some(_)) => self.rewind(),
+                    _ => self.exit = true,
                 }
             }
+            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => match self.pause {
+                None => self.key_event_play_mode(key_event),
+                Some(Pause::Menu) => self.key_event_pause_menu(key_event),
+                Some(Pause::Settings) => self.key_event_settings(key_event),
+            },
+            Event::Resize(width, height) => self.terminal_size = Size::new(width, height),
             _ => {}
         };
-        Ok(())
     }
-}
 
-impl Widget for &GameTui<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from(format!("Your score: {}", self.game.get_score()));
-        let instructions =
-            Line::from("Use 🠀 🠂 🠁 🠃 or h j k l to move, esc to stop/play, q/Q to quit game.");
+    /// Advances the simulation by one fixed step of `period`.
+    ///
+    /// Nothing moves and the clock stands still while paused or too small.
+    ///
+    /// After a crash the final frame blinks for a while before the game ends;
+    /// in practice it blinks until the player rewinds or leaves.
+    fn on_tick(&mut self, period: Duration) {
+        if let Some(crashed_at) = self.crashed_at {
+            self.exit = self.rewind.is_none() && crashed_at.elapsed() >= CRASH_ANIMATION;
+            return;
+        }
 
-        let table = self.game.get_table();
-        let text = table.iter().map(|row| row.join("")).join("\n");
+        if self.pause.is_some()
+            || !self.fits()
+            || self
+                .rewound_at
+                .is_some_and(|at| at.elapsed() < REWIND_PAUSE)
+        {
+            return;
+        }
+
+        self.elapsed += period;
+        for (player, bot) in self.bots.iter_mut() {
+            if let Some(direction) = bot.decide(&self.game, *player) {
+                self.game.rotate(*player, direction);
+            }
+        }
+        if let Some(direction) = self
+            .autopilot
+            .as_mut()
+            .and_then(|autopilot| autopilot.decide(&self.game, 0))
+        {
+            self.game.rotation(direction);
+        }
+
+        if let Some(rewind) = &mut self.rewind {
+            rewind.record(&self.game);
+        }
+        if !self.game.walk() {
+            self.crashed_at = Some(Instant::now());
+        } else if self.is_target_met() {
+            self.exit = true;
+        }
+    }
+
+    fn is_target_met(&self) -> bool {
+        self.game.get_death().is_none()
+            && self.target.is_some_and(|target| target.is_met(&self.game))
+    }
+
+    fn ticker(period: Duration) -> Interval {
+        let mut tick = interval_at(Instant::now() + period, period);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tick
+    }
+
+    /// Takes the last walk of a practice game back.
+    fn rewind(&mut self) {
+        let Some(game) = self.rewind.as_mut().and_then(|rewind| rewind.back()) else {
+            return;
+        };
+
+        self.game = game;
+        self.crashed_at = None;
+        self.rewound_at = Some(Instant::now());
+    }
+
+    fn get_ranking(&self) -> Ranking {
+        match (self.assisted, self.lineup.is_practice()) {
+            (true, _) => Ranking::Unranked,
+            (false, true) => Ranking::Practice,
+            (false, false) => Ranking::Ranked,
+        }
+    }
+
+    fn restart(&mut self, seed: u64) {
+        self.game = self.game.restart(seed);
+        if let Some(rewind) = &mut self.rewind {
+            rewind.clear();
+        }
+        Lineup::reseed(&mut self.bots, seed);
+        self.assisted = self.autopilot.is_some();
+        if let Some(autopilot) = &mut self.autopilot {
+            autopilot.reseed(seed);
+        }
+        self.elapsed = Duration::ZERO;
+        self.crashed_at = None;
+        self.pause = None;
+    }
+
+    /// The smallest terminal that shows the snake with the viewport margins around it.
+    fn required_size(&self) -> Size {
+        let table = self.game.get_board().get_size() as usize + 2;
+        let cells = self.viewport.min_visible((table, table));
+        let (width, height) = self.settings.get_render_mode().size_for(cells);
+
+        Size::new(width, height + TITLE_HEIGHT)
+    }
+
+    fn fits(&self) -> bool {
+        let required = self.required_size();
+
+        self.terminal_size.width >= required.width && self.terminal_size.height >= required.height
+    }
+}
+
+impl GameTui<'_> {
+    fn render_too_small(&self, area: Rect, buf: &mut Buffer) {
+        let required = self.required_size();
+        let text = format!(
+            "Terminal too small, need {}x{} (now {}x{}).\nThe game is paused until it fits.",
+            required.width, required.height, self.terminal_size.width, self.terminal_size.height
+        );
 
         Paragraph::new(text)
-            .block(
-                Block::new()
-                    .title(title.centered())
-                    .title_bottom(instructions.centered()),
-            )
+            .yellow()
             .centered()
+            .wrap(Wrap { trim: true })
             .render(area, buf);
     }
+
+    fn render_menu(
+        title: String,
+        items: Vec<ListItem>,
+        state: &mut ListState,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let width = items.iter().map(|item| item.width()).max().unwrap_or(0) as u16;
+        let area = popup_area(
+            area,
+            width.max(title.len() as u16) + 6,
+            items.len() as u16 + 2,
+        );
+
+        let block = Block::new()
+            .title(Line::raw(title).centered())
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        Clear.render(area, buf);
+        StatefulWidget::render(list, area, buf, state);
+    }
+
+    fn render_pause_menu(&mut self, area: Rect, buf: &mut Buffer) {
+        let items = PAUSE_ITEMS
+            .iter()
+            .map(|item| ListItem::from(*item))
+            .collect();
+
+        Self::render_menu(
+            " Paused ".to_string(),
+            items,
+            &mut self.pause_menu,
+            area,
+            buf,
+        );
+    }
+
+    fn render_settings(&mut self, area: Rect, buf: &mut Buffer) {
+        let items = vec![
+            ListItem::from(format!(
+                "Tick length: ◀ {} ms ▶",
+                self.settings.get_tick().as_millis()
+            )),
+            ListItem::from(format!(
+                "View: ◀ {} ▶",
+                self.settings.get_render_mode().get_name()
+            )),
+            ListItem::from(format!(
+                "Viewport margin: ◀ {} ▶",
+                self.settings.get_viewport_margin()
+            )),
+            ListItem::from(format!(
+                "Path hint: ◀ {} ▶",
+                match self.settings.has_path_hint() {
+                    true => "on",
+                    false => "off",
+                }
+            )),
+            ListItem::from("Back"),
+        ];
+
+        Self::render_menu(
+            " Settings ".to_string(),
+            items,
+            &mut self.settings_menu,
+            area,
+            buf,
+        );
+    }
+}
+
+/// Which player turns where on a shared keyboard: WASD, arrows, IJKL and the numpad.
+fn player_key(code: KeyCode) -> Option<(usize, Direction)> {
+    let key = match code {
+        KeyCode::Char('w') | KeyCode::Char('W') => (0, Direction::Up),
+        KeyCode::Char('s') | KeyCode::Char('S') => (0, Direction::Down),
+        KeyCode::Char('a') | KeyCode::Char('A') => (0, Direction::Left),
+        KeyCode::Char('d') | KeyCode::Char('D') => (0, Direction::Right),
+        KeyCode::Up => (1, Direction::Up),
+        KeyCode::Down => (1, Direction::Down),
+        KeyCode::Left => (1, Direction::Left),
+        KeyCode::Right => (1, Direction::Right),
+        KeyCode::Char('i') | KeyCode::Char('I') => (2, Direction::Up),
+        KeyCode::Char('k') | KeyCode::Char('K') => (2, Direction::Down),
+        KeyCode::Char('j') | KeyCode::Char('J') => (2, Direction::Left),
+        KeyCode::Char('l') | KeyCode::Char('L') => (2, Direction::Right),
+        KeyCode::Char('8') => (3, Direction::Up),
+        KeyCode::Char('5') | KeyCode::Char('2') => (3, Direction::Down),
+        KeyCode::Char('4') => (3, Direction::Left),
+        KeyCode::Char('6') => (3, Direction::Right),
+        _ => return None,
+    };
+
+    Some(key)
+}
+
+pub fn player_color(index: usize) -> Color {
+    PLAYER_COLORS[index % PLAYER_COLORS.len()]
+}
+
+/// Every player's score in their color, struck through once they are out.
+pub fn player_scores(game: &Game) -> Vec<Span<'static>> {
+    game.get_players()
+        .iter()
+        .enumerate()
+        .map(|(index, player)| {
+            let span = Span::from(format!("P{}: {}   ", index + 1, player.get_score()));
+            match player.is_alive() {
+                true => span.fg(player_color(index)),
+                false => span.dark_gray().crossed_out(),
+            }
+        })
+        .collect()
+}
+
+/// The cells between the first snake's head and the food on a shortest way
+/// that avoids the walls and every snake, if there is one.
+pub fn path_hint(game: &Game) -> Vec<Point> {
+    let player = &game.get_players()[0];
+    let food = game.get_food();
+
+    if !player.is_alive() {
+        return Vec::new();
+    }
+
+    Grid::new(game)
+        .path(player.get_snake_head(), |point| point == food, usize::MAX)
+        .map(|path| {
+            path.into_iter()
+                .map(|(_, point)| point)
+                .filter(|point| point != food)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// How a tile of a board is colored.
+pub fn tile_style(tile: Tile) -> Style {
+    match tile {
+        Tile::Floor | Tile::Wall => Style::new(),
+        Tile::OneWay(_) => Style::new().blue(),
+        Tile::Mud => Style::new().yellow().dim(),
+        Tile::Ice => Style::new().light_cyan(),
+        Tile::SpeedPad => Style::new().green(),
+        Tile::Breakable => Style::new().dark_gray(),
+        Tile::Portal(_) => Style::new().light_magenta().bold(),
+    }
+}
+
+/// Whether a blinking highlight that started at `since` is lit right now.
+pub fn blink_on(since: Instant) -> bool {
+    (since.elapsed().as_millis() / BLINK.as_millis()).is_multiple_of(2)
+}
+
+/// Draws the board of `game` through `viewport`, following the snake's head.
+///
+/// With several players every snake has its own color, dead ones are grayed
+/// out and the view stays on the middle of the board.
+///
+/// With `highlight_death` the head and what it crashed into stand out, and
+/// the cells of `hint` are dotted.
+pub fn render_game(
+    game: &Game,
+    viewport: &mut Viewport,
+    mode: RenderMode,
+    highlight_death: bool,
+    hint: &[Point],
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let mut table = game.get_table();
+    let mut styles = render_mode::unstyled(&table);
+    let cell = |point: &Point| ((point.get_x() + 1) as usize, (point.get_y() + 1) as usize);
+
+    for placed in game.get_board().get_tiles() {
+        let tile = placed.get_tile();
+        if tile == Tile::Breakable && !game.is_wall(placed.get_point()) {
+            continue;
+        }
+        let (row, column) = cell(placed.get_point());
+        styles[row][column] = tile_style(tile);
+    }
+
+    for (row, column) in hint.iter().map(cell) {
+        table[row][column] = " ·".to_string();
+        styles[row][column] = Style::new().dark_gray();
+    }
+
+    let food = cell(game.get_food());
+    styles[food.0][food.1] = Style::new().red();
+    for item in game.get_items() {
+        let (row, column) = cell(item.get_point());
+        styles[row][column] = match item.get_kind() {
+            FoodKind::Normal => Style::new().red(),
+            FoodKind::Bonus => Style::new().yellow(),
+            FoodKind::Poison => Style::new().magenta(),
+            FoodKind::Golden => Style::new().light_yellow().bold(),
+        };
+    }
+    for power_up in game.get_power_ups() {
+        let (row, column) = cell(power_up.get_point());
+        styles[row][column] = Style::new().cyan().bold();
+    }
+
+    let players = game.get_players();
+    if players.len() > 1 {
+        for (index, player) in players.iter().enumerate() {
+            let color = match player.is_alive() {
+                true => player_color(index),
+                false => Color::DarkGray,
+            };
+
+            for point in player.get_snake() {
+                let (row, column) = cell(point);
+                styles[row][column] = Style::new().fg(color);
+            }
+        }
+    }
+    for mover in game.get_movers() {
+        let (row, column) = cell(mover.get_point());
+        styles[row][column] = Style::new().red();
+    }
+
+    if highlight_death {
+        for player in players {
+            if let Some(death) = player.get_death() {
+                let obstacle = cell(death.get_obstacle());
+                let head = cell(player.get_snake_head());
+                styles[obstacle.0][obstacle.1] = Style::new().black().on_red();
+                styles[head.0][head.1] = Style::new().black().on_yellow();
+            }
+        }
+    }
+
+    let focus = match players.len() {
+        1 => cell(game.get_snake_head()),
+        _ => (table.len() / 2, table.len() / 2),
+    };
+
+    viewport.render(&table, &styles, focus, mode, area, buf);
+}
+
+impl Widget for &mut GameTui<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.fits() {
+            self.render_too_small(area, buf);
+            return;
+        }
+
+        let time = match self.game.get_ticks_left() {
+            Some(left) => {
+                let left = (self.settings.get_tick() * left).as_secs();
+                format!("Time left: {:02}:{:02}", left / 60, left % 60)
+            }
+            None => {
+                let elapsed = self.elapsed.as_secs();
+                format!("Time: {:02}:{:02}", elapsed / 60, elapsed % 60)
+            }
+        };
+        let status = match self.game.get_mode() {
+            GameMode::Survival => {
+                format!("Obstacles: {}   {}", self.game.get_obstacles().len(), time)
+            }
+            GameMode::Zen if !self.is_multiplayer() => format!(
+                "Length: {}   {}   Zen",
+                self.game.get_players()[0].get_snake().len(),
+                time
+            ),
+            GameMode::Zen => format!("{}   Zen", time),
+            GameMode::Classic | GameMode::TimeAttack => time,
+        };
+        let status = match &self.rewind {
+            Some(rewind) => format!(
+                "{}   Rewind: {}s",
+                status,
+                (self.settings.get_tick() * rewind.len() as u32).as_secs()
+            ),
+            None => status,
+        };
+        let status =
+            match self.is_multiplayer() {
+                false => self.game.get_players()[0].get_effects().iter().fold(
+                    status,
+                    |status, effect| {
+                        format!(
+                            "{}   {} {}",
+                            status,
+                            effect.get_kind().get_name(),
+                            effect.get_ticks_left()
+                        )
+                    },
+                ),
+                true => status,
+            };
+        let status = match self.target {
+            Some(target) => format!("{}   Goal: {}", status, target.describe()),
+            None => status,
+        };
+        let mut title = match self.is_multiplayer() {
+            false => {
+                let points = self
+                    .game
+                    .get_mode()
+                    .points(self.game.get_score(), self.game.get_ticks());
+                Line::from(format!("Your score: {}   {}", points, status))
+            }
+            true => {
+                let mut spans = player_scores(&self.game);
+                spans.push(Span::from(status));
+                Line::from(spans)
+            }
+        };
+        if self.autopilot.is_some() {
+            title.push_span(Span::from("   Autopilot").yellow());
+        }
+        let instructions = match self.pause {
+            None if self.lineup.get_humans() > 1 => format!(
+                "P1 w a s d, P2 🠀 🠂 🠁 🠃, P3 i j k l, P4 numpad 8 4 5 6 to move, v/V to change view ({}), esc to pause, q/Q to quit game.",
+                self.settings.get_render_mode().get_name()
+            ),
+            None if self.rewind.is_some() && self.crashed_at.is_some() => {
+                "Hold r/R to rewind, any other key to finish.".to_string()
+            }
+            None if self.rewind.is_some() => format!(
+                "Use 🠀 🠂 🠁 🠃 or h j k l to move, hold r/R to rewind, v/V to change view ({}), g/G for a path hint, o/O for autopilot, esc to pause, q/Q to quit game.",
+                self.settings.get_render_mode().get_name()
+            ),
+            None => format!(
+                "Use 🠀 🠂 🠁 🠃 or h j k l to move, v/V to change view ({}), g/G for a path hint, o/O for autopilot, esc to pause, q/Q to quit game.",
+                self.settings.get_render_mode().get_name()
+            ),
+            Some(Pause::Menu) => "Use ↓↑ to move, ⮡ to choose, esc to resume.".to_string(),
+            Some(Pause::Settings) => "Use ↓↑ to move, 🠀 🠂 to change, esc to go back.".to_string(),
+        };
+
+        let block = Block::new()
+            .title(title.centered())
+            .title_bottom(Line::from(instructions).centered());
+        let board_area = block.inner(area);
+        block.render(area, buf);
+
+        let hint = match self.settings.has_path_hint() {
+            true => path_hint(&self.game),
+            false => Vec::new(),
+        };
+
+        render_game(
+            &self.game,
+            &mut self.viewport,
+            self.settings.get_render_mode(),
+            self.crashed_at.is_some_and(blink_on),
+            &hint,
+            board_area,
+            buf,
+        );
+
+        match self.pause {
+            None => {}
+            Some(Pause::Menu) => self.render_pause_menu(board_area, buf),
+            Some(Pause::Settings) => self.render_settings(board_area, buf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_game {
+    use std::collections::LinkedList;
+
+    use super::path_hint;
+    use crate::core::{Board, Direction, Game, Player, Point, Snapshot, Wall};
+
+    #[test]
+    fn path_hint_wraps_around() {
+        let snake = LinkedList::from([Point::new(3, 4), Point::new(3, 3), Point::new(3, 2)]);
+        let players = vec![Player::new(snake, Direction::Right)];
+
+        let board = Board::new("test".to_string(), 7, Vec::new());
+        let game = Game::from_snapshot(
+            &board,
+            Snapshot::new(
+                players.clone(),
+                Point::new(3, 0),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                0,
+            ),
+        );
+        assert_eq!(path_hint(&game), vec![Point::new(3, 5), Point::new(3, 6)]);
+
+        let board = Board::new("test".to_string(), 7, Vec::from([Wall::new(3, 6)]));
+        let game = Game::from_snapshot(
+            &board,
+            Snapshot::new(
+                players,
+                Point::new(3, 0),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                0,
+            ),
+        );
+        assert_eq!(path_hint(&game).len(), 4);
+    }
 }
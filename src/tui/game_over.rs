@@ -1,70 +1,336 @@
-use std::{io, time::Duration};
+use std::{cmp::Ordering, io};
 
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    text::Line,
-    widgets::{Block, Paragraph, Widget},
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    symbols::border,
+    text::{Line, Text},
+    widgets::{
+        Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
+        Widget,
+    },
     DefaultTerminal, Frame,
 };
-use tokio::time::sleep;
 
-use crate::core::Scoreboard;
+use super::{replay::ReplayTui, settings::Settings};
+use crate::core::{Board, Death, GameMode, Ranking, Replay, Scoreboard};
+
+const MAX_NAME_LENGTH: usize = 12;
+const MENU_ITEMS: [&str; 4] = [
+    "Retry same board",
+    "Watch replay",
+    "View scoreboard",
+    "Back to menu",
+];
+
+pub enum GameOverTuiResult {
+    Retry,
+    Scoreboard,
+    Menu,
+}
+
+enum State {
+    Name,
+    Summary,
+}
 
-#[derive(Debug)]
 pub struct GameOverTui {
-    score: u16,
+    board: Board,
+    /// What the game counts for on the scoreboard of its mode.
+    points: u16,
+    /// The scoreboard of the board and mode, practice apart.
+    category: String,
+    replay: Replay,
+    death: Option<Death>,
+    /// Whether the snake filled every open cell of the board.
+    cleared: bool,
+    ranking: Ranking,
+    settings: Settings,
     scoreboard: Scoreboard,
-    board_name: String,
+    player: String,
+    /// The name offered when the prompt opened, put back on Esc.
+    previous: String,
+    rank: usize,
+    personal_best: Option<u16>,
+    state: State,
+    menu: ListState,
+    result: Option<GameOverTuiResult>,
 }
 
 impl GameOverTui {
-    pub fn new(board_name: String, score: u16) -> Self {
-        let mut scoreboard = Scoreboard::new();
-        scoreboard.add(board_name.clone(), score);
+    pub fn new(
+        board: Board,
+        score: u16,
+        replay: Replay,
+        death: Option<Death>,
+        ranking: Ranking,
+        player: String,
+        settings: Settings,
+    ) -> Self {
+        let scoreboard = Scoreboard::new();
+        let mode = replay.get_mode();
+        let points = mode.points(score, replay.get_ticks());
+        let category = mode.category(board.get_name());
+        let recorded = ranking.category(category.clone());
+        let state = match recorded
+            .as_ref()
+            .is_some_and(|category| scoreboard.is_high_score(category, points))
+        {
+            true => State::Name,
+            false => State::Summary,
+        };
+
+        let mut menu = ListState::default();
+        menu.select_first();
 
-        Self {
-            score,
+        let mut game_over_tui = Self {
+            board,
+            points,
+            category: recorded.unwrap_or(category),
+            replay,
+            death,
+            cleared: false,
+            ranking,
+            settings,
             scoreboard,
-            board_name,
+            previous: player.clone(),
+            player,
+            rank: 0,
+            personal_best: None,
+            state,
+            menu,
+            result: None,
+        };
+
+        if matches!(game_over_tui.state, State::Summary) && ranking != Ranking::Unranked {
+            game_over_tui.record();
         }
+
+        game_over_tui
     }
 
-    pub async fn run(&self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        terminal.draw(|frame| self.draw(frame))?;
+    /// Tells the game ended with the board cleared.
+    pub fn cleared(mut self, cleared: bool) -> Self {
+        self.cleared = cleared;
+        self
+    }
+
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<GameOverTuiResult> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Some(result) = self.result.take() {
+                return Ok(result);
+            }
 
-        sleep(Duration::from_millis(3000)).await;
+            match event::read()? {
+                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                    match self.state {
+                        State::Name => self.key_event_name(key_event),
+                        State::Summary => {
+                            if self.key_event_summary(key_event) {
+                                ReplayTui::new(&self.board, &self.replay, self.settings)
+                                    .run(terminal)
+                                    .await?;
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            };
+        }
+    }
 
-        Ok(())
+    /// The name the score was recorded under, to offer it again next time.
+    pub fn get_player(&self) -> &str {
+        &self.player
     }
 
-    fn draw(&self, frame: &mut Frame) {
+    fn draw(&mut self, frame: &mut Frame) {
         frame.render_widget(self, frame.area());
     }
-}
 
-impl Widget for &GameOverTui {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let title = Line::from("Game Over :(!");
+    fn key_event_name(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char(c)
+                if (c.is_alphanumeric() || c == ' ') && self.player.len() < MAX_NAME_LENGTH =>
+            {
+                self.player.push(c)
+            }
+            KeyCode::Backspace => {
+                self.player.pop();
+            }
+            KeyCode::Enter if !self.player.trim().is_empty() => {
+                self.player = self.player.trim().to_string();
+                self.record();
+                self.state = State::Summary;
+            }
+            KeyCode::Esc => {
+                self.player = self.previous.clone();
+                self.record();
+                self.state = State::Summary;
+            }
+            _ => {}
+        }
+    }
 
-        let scores = self.scoreboard.get(&self.board_name).unwrap();
+    /// Returns whether the replay should be shown.
+    fn key_event_summary(&mut self, key_event: KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
+                self.result = Some(GameOverTuiResult::Menu)
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.menu.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.menu.select_previous(),
+            KeyCode::Enter => match self.menu.selected() {
+                Some(0) => self.result = Some(GameOverTuiResult::Retry),
+                Some(1) => return true,
+                Some(2) => self.result = Some(GameOverTuiResult::Scoreboard),
+                Some(3) => self.result = Some(GameOverTuiResult::Menu),
+                _ => {}
+            },
+            _ => {}
+        }
 
-        let suffix = match self.score == scores[0] {
-            true => "The best record.".to_string(),
-            false => format!("The best record is {}", scores[0]),
-        };
-        let text = format!(
-            "Your score is {} in the {} board.\n{}",
-            self.score, self.board_name, suffix
-        );
+        false
+    }
+
+    fn record(&mut self) {
+        self.personal_best = self.scoreboard.personal_best(&self.category, &self.player);
+        self.rank = self.scoreboard.rank(&self.category, self.points);
+        self.scoreboard
+            .add(self.category.clone(), self.player.clone(), self.points);
+    }
+}
+
+impl GameOverTui {
+    fn personal_best_line(&self) -> Line<'_> {
+        match self.personal_best {
+            None if self.player.is_empty() => Line::from("Your first score on this board."),
+            None => Line::from(format!("Your first score on this board, {}.", self.player)),
+            Some(best) => match self.points.cmp(&best) {
+                Ordering::Greater => Line::from(format!(
+                    "New personal best, +{} over {}!",
+                    self.points - best,
+                    best
+                ))
+                .green(),
+                Ordering::Equal => Line::from("Equal to your personal best."),
+                Ordering::Less => Line::from(format!(
+                    "{} below your personal best of {}.",
+                    best - self.points,
+                    best
+                ))
+                .yellow(),
+            },
+        }
+    }
+
+    fn render_name(&self, area: Rect, buf: &mut Buffer) {
+        let text = Text::from(vec![
+            Line::from(format!(
+                "New high score: {} in the {} board!",
+                self.points, self.category
+            ))
+            .bold(),
+            Line::from(""),
+            Line::from(format!("{}_", self.player)),
+        ]);
 
         Paragraph::new(text)
             .block(
                 Block::new()
-                    .border_type(ratatui::widgets::BorderType::Rounded)
-                    .title(title.centered()),
+                    .title(Line::from("Please enter your name").centered())
+                    .title_bottom(
+                        Line::from("Use Enter to save, Esc to leave the name out.").centered(),
+                    ),
             )
             .centered()
             .render(area, buf);
     }
+
+    /// Whether a timed game ran until the end of its time.
+    fn is_out_of_time(&self) -> bool {
+        self.replay
+            .get_mode()
+            .get_time_limit()
+            .is_some_and(|limit| self.replay.get_ticks() >= limit)
+    }
+
+    fn render_summary(&mut self, area: Rect, buf: &mut Buffer) {
+        let [summary_area, menu_area] =
+            Layout::vertical([Constraint::Length(6), Constraint::Fill(1)]).areas(area);
+
+        let total = self.scoreboard.get(&self.category).map_or(0, |s| s.len());
+
+        let cause = match &self.death {
+            Some(death) => Line::from(death.describe()).red(),
+            None if self.cleared => Line::from("You cleared the board!").green(),
+            None if self.is_out_of_time() => Line::from("Time is up!").green(),
+            None => Line::from("You quit the game."),
+        };
+
+        let mut lines = vec![
+            cause,
+            Line::from(match self.replay.get_mode() {
+                GameMode::Survival => format!(
+                    "You survived {} walks in the {} board.",
+                    self.points, self.category
+                ),
+                _ => format!(
+                    "Your score is {} in the {} board.",
+                    self.points, self.category
+                ),
+            }),
+        ];
+        match self.ranking {
+            Ranking::Unranked => lines
+                .push(Line::from("The autopilot played, so the score is not recorded.").yellow()),
+            Ranking::Ranked | Ranking::Practice => lines.extend([
+                Line::from(format!("Rank #{} of {}.", self.rank, total)),
+                self.personal_best_line(),
+            ]),
+        }
+        let text = Text::from(lines);
+
+        Paragraph::new(text)
+            .block(Block::new().title(Line::from("Game Over :(!").centered()))
+            .centered()
+            .render(summary_area, buf);
+
+        let [_, menu_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(24),
+            Constraint::Fill(1),
+        ])
+        .areas(menu_area);
+
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+
+        let items: Vec<_> = MENU_ITEMS
+            .iter()
+            .map(|item| ListItem::from(*item))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, menu_area, buf, &mut self.menu);
+    }
+}
+
+impl Widget for &mut GameOverTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        match self.state {
+            State::Name => self.render_name(area, buf),
+            State::Summary => self.render_summary(area, buf),
+        }
+    }
 }
@@ -0,0 +1,159 @@
+use crate::core::{Bot, Difficulty, GameMode, Genome, GENOME_PATH};
+
+use serde::{Deserialize, Serialize};
+use std::io;
+
+const MAX_PLAYERS: usize = 4;
+
+/// Who plays the next game, people sharing the keyboard and then computer
+/// opponents, in which mode and whether it is practice.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Lineup {
+    humans: usize,
+    bots: usize,
+    difficulty: Difficulty,
+    mode: GameMode,
+    /// Practice games can be rewound and keep their scores apart.
+    #[serde(default)]
+    practice: bool,
+}
+
+impl Default for Lineup {
+    fn default() -> Self {
+        Self {
+            humans: 1,
+            bots: 0,
+            difficulty: Difficulty::default(),
+            mode: GameMode::default(),
+            practice: false,
+        }
+    }
+}
+
+impl Lineup {
+    /// A single player of `mode`.
+    pub fn solo(mode: GameMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    pub fn get_humans(&self) -> usize {
+        self.humans
+    }
+
+    /// Every snake of the game, the humans first.
+    pub fn get_players(&self) -> usize {
+        self.humans + self.bots
+    }
+
+    pub fn get_mode(&self) -> GameMode {
+        self.mode
+    }
+
+    pub fn is_practice(&self) -> bool {
+        self.practice
+    }
+
+    pub fn next_humans(&mut self) {
+        self.humans = self.humans % MAX_PLAYERS + 1;
+        self.bots = self.bots.min(MAX_PLAYERS - self.humans);
+    }
+
+    pub fn next_bots(&mut self) {
+        self.bots = (self.bots + 1) % (MAX_PLAYERS - self.humans + 1);
+    }
+
+    pub fn next_difficulty(&mut self) {
+        self.difficulty = self.difficulty.next(Genome::load(GENOME_PATH).is_ok());
+    }
+
+    pub fn next_mode(&mut self) {
+        self.mode = self.mode.next();
+    }
+
+    pub fn toggle_practice(&mut self) {
+        self.practice = !self.practice;
+    }
+
+    pub fn is_bot(&self, player: usize) -> bool {
+        player >= self.humans
+    }
+
+    /// The bots of a game started from `seed`, each with its player index.
+    pub fn bots(&self, seed: u64) -> io::Result<Vec<(usize, Bot)>> {
+        (self.humans..self.get_players())
+            .map(|player| Ok((player, self.difficulty.bot(Self::bot_seed(seed, player))?)))
+            .collect()
+    }
+
+    /// Starts `bots` over for a game started from `seed`, as if they were
+    /// made for it.
+    pub fn reseed(bots: &mut [(usize, Bot)], seed: u64) {
+        for (player, bot) in bots {
+            bot.reseed(Self::bot_seed(seed, *player));
+        }
+    }
+
+    pub fn get_name(&self, player: usize) -> String {
+        match self.is_bot(player) {
+            true => format!("Bot {}", player + 1),
+            false => format!("Player {}", player + 1),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let humans = match self.humans {
+            1 => "1 player".to_string(),
+            humans => format!("{} players", humans),
+        };
+
+        match self.bots {
+            0 => humans,
+            1 => format!("{}, 1 {} bot", humans, self.difficulty.get_name()),
+            bots => format!("{}, {} {} bots", humans, bots, self.difficulty.get_name()),
+        }
+    }
+}
+
+impl Lineup {
+    fn bot_seed(seed: u64, player: usize) -> u64 {
+        seed ^ player as u64
+    }
+}
+
+#[cfg(test)]
+mod test_lineup {
+    use super::Lineup;
+    use crate::core::GameMode;
+
+    #[test]
+    fn at_most_four_snakes() {
+        let mut lineup = Lineup::default();
+        assert_eq!(lineup.describe(), "1 player");
+
+        lineup.next_bots();
+        lineup.next_bots();
+        lineup.next_bots();
+        assert_eq!(lineup.get_players(), 4);
+        assert_eq!(lineup.describe(), "1 player, 3 medium bots");
+
+        lineup.next_humans();
+        assert_eq!(lineup.get_players(), 4);
+        assert!(lineup.is_bot(2));
+        assert!(!lineup.is_bot(1));
+
+        lineup.next_bots();
+        assert_eq!(lineup.get_players(), 2);
+        lineup.next_difficulty();
+        assert_eq!(lineup.describe(), "2 players");
+
+        lineup.next_mode();
+        assert_eq!(lineup.get_mode(), GameMode::TimeAttack);
+        assert_eq!(Lineup::solo(GameMode::Zen).get_players(), 1);
+
+        lineup.toggle_practice();
+        assert!(lineup.is_practice());
+    }
+}
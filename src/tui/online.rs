@@ -0,0 +1,357 @@
+use crate::{
+    core::{Board, Direction, Game, Snapshot},
+    net::{Client, ClientMessage, Lobby, ServerMessage},
+};
+
+use super::{
+    game::{blink_on, player_color, player_scores, render_game},
+    render_mode,
+    settings::Settings,
+    viewport::Viewport,
+};
+
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind};
+use futures::StreamExt;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    symbols::border,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+    DefaultTerminal, Frame,
+};
+use std::io;
+use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
+
+const FRAME: Duration = Duration::from_millis(16);
+
+/// A game hosted by the server, drawn from the snapshots it sends.
+struct OnlineGame {
+    board: Board,
+    players: Vec<String>,
+    you: usize,
+    snapshot: Option<Snapshot>,
+    finished: Option<(Option<usize>, Instant)>,
+}
+
+/// The screens of a player joined to a `Server`: its lobby and its games.
+pub struct OnlineTui {
+    client: Client,
+    settings: Settings,
+    viewport: Viewport,
+    preview: Viewport,
+    lobby: Option<Lobby>,
+    game: Option<OnlineGame>,
+    notice: Option<String>,
+    disconnected: bool,
+    exit: bool,
+}
+
+impl OnlineTui {
+    pub fn new(client: Client, settings: Settings) -> Self {
+        Self {
+            client,
+            settings,
+            viewport: Viewport::new(settings.get_viewport_margin()),
+            preview: Viewport::centered(),
+            lobby: None,
+            game: None,
+            notice: None,
+            disconnected: false,
+            exit: false,
+        }
+    }
+
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let mut events = EventStream::new();
+        let mut frame = interval(FRAME);
+        frame.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        while !self.exit {
+            tokio::select! {
+                _ = frame.tick() => {
+                    terminal.draw(|frame| self.draw(frame))?;
+                }
+                Some(event) = events.next() => self.handle_event(event?).await,
+                message = self.client.receive(), if !self.disconnected => self.on_message(message),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+    }
+
+    fn on_message(&mut self, message: io::Result<Option<ServerMessage>>) {
+        let message = match message {
+            Ok(Some(message)) => message,
+            Ok(None) => return self.disconnected = true,
+            Err(error) => {
+                self.notice = Some(error.to_string());
+                return self.disconnected = true;
+            }
+        };
+
+        match message {
+            ServerMessage::Lobby(lobby) => self.lobby = Some(lobby),
+            ServerMessage::Start {
+                board,
+                players,
+                you,
+            } => {
+                self.notice = None;
+                self.game = Some(OnlineGame {
+                    board,
+                    players,
+                    you,
+                    snapshot: None,
+                    finished: None,
+                });
+            }
+            ServerMessage::Snapshot(snapshot) => {
+                if let Some(game) = self.game.as_mut() {
+                    game.snapshot = Some(snapshot);
+                }
+            }
+            ServerMessage::GameOver { winner } => {
+                if let Some(game) = self.game.as_mut() {
+                    game.finished = Some((winner, Instant::now()));
+                }
+            }
+            ServerMessage::Error { message } => self.notice = Some(message),
+        }
+    }
+
+    async fn handle_event(&mut self, event: Event) {
+        if let Event::Key(key_event) = event {
+            if key_event.kind == KeyEventKind::Press {
+                if let Some(message) = self.key_event(key_event) {
+                    if self.client.send(&message).await.is_err() {
+                        self.disconnected = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handles `key_event` and returns what to tell the server about it.
+    fn key_event(&mut self, key_event: KeyEvent) -> Option<ClientMessage> {
+        if self.disconnected {
+            self.exit = true;
+            return None;
+        }
+
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
+                self.exit = true;
+                return None;
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                self.settings.next_render_mode();
+                return None;
+            }
+            _ => {}
+        }
+
+        match &self.game {
+            Some(game) if game.finished.is_some() => {
+                self.game = None;
+                None
+            }
+            Some(_) => Self::key_event_game(key_event),
+            None => self.key_event_lobby(key_event),
+        }
+    }
+
+    fn key_event_game(key_event: KeyEvent) -> Option<ClientMessage> {
+        let direction = match key_event.code {
+            KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('k') => Direction::Up,
+            KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('j') => Direction::Down,
+            KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('h') => Direction::Left,
+            KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('l') => Direction::Right,
+            _ => return None,
+        };
+
+        Some(ClientMessage::Turn { direction })
+    }
+
+    fn key_event_lobby(&self, key_event: KeyEvent) -> Option<ClientMessage> {
+        let lobby = self.lobby.as_ref().filter(|lobby| lobby.is_host())?;
+        let boards = lobby.get_boards().len();
+        let selected = lobby.get_selected();
+        lobby.get_boards().get(selected)?;
+
+        match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(ClientMessage::SelectBoard {
+                index: (selected + 1) % boards,
+            }),
+            KeyCode::Char('k') | KeyCode::Up => Some(ClientMessage::SelectBoard {
+                index: (selected + boards - 1) % boards,
+            }),
+            KeyCode::Enter => Some(ClientMessage::Start),
+            _ => None,
+        }
+    }
+}
+
+impl OnlineTui {
+    fn render_disconnected(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![Line::from("Disconnected from the server.").bold()];
+        if let Some(notice) = &self.notice {
+            lines.push(Line::from(notice.as_str()).red());
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Press any key to leave."));
+
+        Paragraph::new(Text::from(lines))
+            .centered()
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+
+    fn render_lobby(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(lobby) = &self.lobby else {
+            Paragraph::new("Connecting…").centered().render(area, buf);
+            return;
+        };
+
+        let [header_area, main_area, notice_area, footer_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let [players_area, board_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+                .areas(main_area);
+
+        Paragraph::new("Lobby")
+            .bold()
+            .centered()
+            .render(header_area, buf);
+
+        let players: Vec<Line> = lobby
+            .get_players()
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let mut label = format!("P{}: {}", index + 1, name);
+                if index == 0 {
+                    label.push_str(" (host)");
+                }
+                if index == lobby.get_you() {
+                    label.push_str(" (you)");
+                }
+                Line::from(label).fg(player_color(index))
+            })
+            .collect();
+
+        Paragraph::new(Text::from(players))
+            .block(
+                Block::new()
+                    .title(Line::raw(" Players ").centered())
+                    .borders(Borders::ALL)
+                    .border_set(border::ROUNDED),
+            )
+            .render(players_area, buf);
+
+        let board = lobby.get_board();
+        let table = board.get_table();
+        let center = table.len() / 2;
+
+        let block = Block::new()
+            .title(Line::raw(format!(" {} ", board.get_name())).centered())
+            .title_bottom(
+                Line::raw(format!(" {} ", self.settings.get_render_mode().get_name()))
+                    .right_aligned(),
+            )
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+        let preview_area = block.inner(board_area);
+        block.render(board_area, buf);
+
+        self.preview.render(
+            &table,
+            &render_mode::unstyled(&table),
+            (center, center),
+            self.settings.get_render_mode(),
+            preview_area,
+            buf,
+        );
+
+        if let Some(notice) = &self.notice {
+            Paragraph::new(notice.as_str())
+                .red()
+                .centered()
+                .render(notice_area, buf);
+        }
+
+        let footer = match lobby.is_host() {
+            true => "Use ↓↑ to choose the board, ⮡ to start, v/V to change view, q/Q to leave.",
+            false => "Waiting for the host to start, v/V to change view, q/Q to leave.",
+        };
+        Paragraph::new(footer).centered().render(footer_area, buf);
+    }
+}
+
+impl OnlineGame {
+    fn render(&self, viewport: &mut Viewport, settings: &Settings, area: Rect, buf: &mut Buffer) {
+        let Some(snapshot) = &self.snapshot else {
+            Paragraph::new("Get ready…").centered().render(area, buf);
+            return;
+        };
+        let game = Game::from_snapshot(&self.board, snapshot.clone());
+
+        let mut title = player_scores(&game);
+        if let Some(name) = self.players.get(self.you) {
+            title.push(Span::from(format!("You are {name}")).fg(player_color(self.you)));
+        }
+
+        let instructions = match self.finished {
+            Some((Some(winner), _)) => match self.players.get(winner) {
+                Some(name) => format!("{name} wins! Press any key to go back to the lobby."),
+                None => "The game is over! Press any key to go back to the lobby.".to_string(),
+            },
+            Some((None, _)) => "It's a draw! Press any key to go back to the lobby.".to_string(),
+            None => format!(
+                "Use 🠀 🠂 🠁 🠃 or h j k l to move, v/V to change view ({}), q/Q to leave.",
+                settings.get_render_mode().get_name()
+            ),
+        };
+
+        let block = Block::new()
+            .title(Line::from(title).centered())
+            .title_bottom(Line::from(instructions).centered());
+        let board_area = block.inner(area);
+        block.render(area, buf);
+
+        render_game(
+            &game,
+            viewport,
+            settings.get_render_mode(),
+            self.finished.is_some_and(|(_, at)| blink_on(at)),
+            &[],
+            board_area,
+            buf,
+        );
+    }
+}
+
+impl Widget for &mut OnlineTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.disconnected {
+            self.render_disconnected(area, buf);
+            return;
+        }
+
+        match &self.game {
+            Some(game) => game.render(&mut self.viewport, &self.settings, area, buf),
+            None => self.render_lobby(area, buf),
+        }
+    }
+}
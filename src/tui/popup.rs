@@ -0,0 +1,13 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+
+/// A `width` x `height` rectangle in the middle of `area`, shrunk to fit if needed.
+pub fn popup_area(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    area
+}
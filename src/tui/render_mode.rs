@@ -0,0 +1,287 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span, Text},
+};
+
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+const WALL: &str = "██";
+/// Walls left unstyled, in the modes where their glyph looks like any other
+/// filled cell.
+const WALL_STYLE: Style = Style::new().fg(Color::DarkGray);
+
+/// How table cells are packed into terminal characters.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RenderMode {
+    /// Picks the first mode in which the whole table fits.
+    #[default]
+    Auto,
+    /// Two columns per cell, the classic look.
+    Wide,
+    /// One column per cell.
+    Compact,
+    /// Two rows of cells per character.
+    HalfBlock,
+    /// A 4x2 block of cells per braille character.
+    Braille,
+}
+
+impl RenderMode {
+    pub fn next(self) -> Self {
+        match self {
+            RenderMode::Auto => RenderMode::Wide,
+            RenderMode::Wide => RenderMode::Compact,
+            RenderMode::Compact => RenderMode::HalfBlock,
+            RenderMode::HalfBlock => RenderMode::Braille,
+            RenderMode::Braille => RenderMode::Auto,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            RenderMode::Auto => "auto",
+            RenderMode::Wide => "wide",
+            RenderMode::Compact => "compact",
+            RenderMode::HalfBlock => "half-block",
+            RenderMode::Braille => "braille",
+        }
+    }
+
+    /// Replaces `Auto` with the least dense mode that shows a `size` table in `area`.
+    pub fn resolve(self, size: (usize, usize), area: Rect) -> Self {
+        if self != RenderMode::Auto {
+            return self;
+        }
+
+        [RenderMode::Wide, RenderMode::Compact, RenderMode::HalfBlock]
+            .into_iter()
+            .find(|mode| {
+                let (rows, columns) = mode.capacity(area);
+                rows >= size.0 && columns >= size.1
+            })
+            .unwrap_or(RenderMode::Braille)
+    }
+
+    /// Number of `(rows, columns)` table cells that fit in `area`.
+    pub fn capacity(&self, area: Rect) -> (usize, usize) {
+        let (height, width) = (area.height as usize, area.width as usize);
+
+        match self {
+            RenderMode::Auto | RenderMode::Wide => (height, width / 2),
+            RenderMode::Compact => (height, width),
+            RenderMode::HalfBlock => (height * 2, width),
+            RenderMode::Braille => (height * 4, width * 2),
+        }
+    }
+
+    /// Terminal `(width, height)` needed to show `(rows, columns)` table cells.
+    ///
+    /// `Auto` asks for as much as `Compact`, the densest mode that still draws
+    /// every cell as its own character.
+    pub fn size_for(&self, cells: (usize, usize)) -> (u16, u16) {
+        let (rows, columns) = cells;
+
+        let (width, height) = match self {
+            RenderMode::Auto | RenderMode::Compact => (columns, rows),
+            RenderMode::Wide => (columns * 2, rows),
+            RenderMode::HalfBlock => (columns, rows.div_ceil(2)),
+            RenderMode::Braille => (columns.div_ceil(2), rows.div_ceil(4)),
+        };
+
+        (width as u16, height as u16)
+    }
+
+    /// Draws `table`; `styles` has the same shape and colors the matching cells.
+    pub fn render(&self, table: &[Vec<String>], styles: &[Vec<Style>]) -> Text<'static> {
+        let lines = match self {
+            RenderMode::Auto | RenderMode::Wide => {
+                Self::render_cells(table, styles, |cell| cell.to_string())
+            }
+            RenderMode::Compact => Self::render_cells(table, styles, |cell| {
+                cell.chars().last().unwrap_or(' ').to_string()
+            }),
+            RenderMode::HalfBlock => Self::render_blocks(table, styles, (2, 1), |filled| {
+                match (filled[0][0], filled[1][0]) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                }
+            }),
+            RenderMode::Braille => Self::render_blocks(table, styles, (4, 2), |filled| {
+                let mut dots = 0;
+                for (row, bits) in BRAILLE_DOTS.iter().enumerate() {
+                    for (column, bit) in bits.iter().enumerate() {
+                        if filled[row][column] {
+                            dots |= bit;
+                        }
+                    }
+                }
+                char::from_u32(0x2800 + dots).unwrap()
+            }),
+        };
+
+        Text::from(lines)
+    }
+}
+
+/// A style table of the same shape as `table` with every cell unstyled.
+pub fn unstyled(table: &[Vec<String>]) -> Vec<Vec<Style>> {
+    table
+        .iter()
+        .map(|row| vec![Style::default(); row.len()])
+        .collect()
+}
+
+impl RenderMode {
+    fn render_cells(
+        table: &[Vec<String>],
+        styles: &[Vec<Style>],
+        glyph: impl Fn(&str) -> String,
+    ) -> Vec<Line<'static>> {
+        table
+            .iter()
+            .zip(styles)
+            .map(|(row, row_styles)| {
+                row.iter()
+                    .zip(row_styles)
+                    .map(|(cell, style)| Span::styled(glyph(cell), *style))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Packs `block` = `(rows, columns)` cells into one character chosen by `glyph`
+    /// from which cells of the block are not empty. A block of nothing but
+    /// unstyled walls is drawn in `WALL_STYLE`.
+    fn render_blocks(
+        table: &[Vec<String>],
+        styles: &[Vec<Style>],
+        block: (usize, usize),
+        glyph: impl Fn(&[[bool; 2]; 4]) -> char,
+    ) -> Vec<Line<'static>> {
+        let columns = table.first().map_or(0, |row| row.len());
+
+        (0..table.len())
+            .step_by(block.0)
+            .map(|top| {
+                (0..columns)
+                    .step_by(block.1)
+                    .map(|left| {
+                        let mut filled = [[false; 2]; 4];
+                        let mut style = Style::default();
+                        let (mut walls, mut others) = (false, false);
+
+                        for row in 0..block.0 {
+                            for column in 0..block.1 {
+                                let Some(cell) = table
+                                    .get(top + row)
+                                    .and_then(|cells| cells.get(left + column))
+                                else {
+                                    continue;
+                                };
+
+                                let cell_style = styles[top + row][left + column];
+                                filled[row][column] = !cell.trim().is_empty();
+                                if cell == WALL && cell_style == Style::default() {
+                                    walls = true;
+                                    continue;
+                                }
+                                others |= filled[row][column];
+                                if style == Style::default() {
+                                    style = cell_style;
+                                }
+                            }
+                        }
+                        if walls && !others {
+                            style = WALL_STYLE;
+                        }
+
+                        Span::styled(glyph(&filled).to_string(), style)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test_render_mode {
+    use ratatui::{layout::Rect, style::Style};
+
+    use super::{unstyled, RenderMode, WALL_STYLE};
+
+    fn table(rows: &[&str]) -> (Vec<Vec<String>>, Vec<Vec<Style>>) {
+        let table: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .map(|c| if c == '#' { "██" } else { "  " }.to_string())
+                    .collect()
+            })
+            .collect();
+        let styles = unstyled(&table);
+
+        (table, styles)
+    }
+
+    #[test]
+    fn resolve() {
+        let area = Rect::new(0, 0, 40, 20);
+
+        assert_eq!(RenderMode::Auto.resolve((20, 20), area), RenderMode::Wide);
+        assert_eq!(
+            RenderMode::Auto.resolve((20, 40), area),
+            RenderMode::Compact
+        );
+        assert_eq!(
+            RenderMode::Auto.resolve((40, 40), area),
+            RenderMode::HalfBlock
+        );
+        assert_eq!(
+            RenderMode::Auto.resolve((60, 60), area),
+            RenderMode::Braille
+        );
+        assert_eq!(
+            RenderMode::Compact.resolve((60, 60), area),
+            RenderMode::Compact
+        );
+    }
+
+    #[test]
+    fn size_for() {
+        assert_eq!(RenderMode::Wide.size_for((9, 9)), (18, 9));
+        assert_eq!(RenderMode::Auto.size_for((9, 9)), (9, 9));
+        assert_eq!(RenderMode::HalfBlock.size_for((9, 9)), (9, 5));
+        assert_eq!(RenderMode::Braille.size_for((9, 9)), (5, 3));
+    }
+
+    #[test]
+    fn render() {
+        let (cells, styles) = table(&["#.", ".#", "##"]);
+
+        assert_eq!(
+            RenderMode::Compact.render(&cells, &styles).to_string(),
+            "█ \n █\n██"
+        );
+        assert_eq!(
+            RenderMode::HalfBlock.render(&cells, &styles).to_string(),
+            "▀▄\n▀▀"
+        );
+        assert_eq!(RenderMode::Braille.render(&cells, &styles).to_string(), "⠵");
+    }
+
+    #[test]
+    fn walls_stand_out_from_snakes() {
+        let (mut cells, styles) = table(&["#.", "..", ".#", ".."]);
+        cells[2][1] = "──".to_string();
+
+        let lines = RenderMode::HalfBlock.render(&cells, &styles).lines;
+        assert_eq!(lines[0].spans[0].style, WALL_STYLE);
+        assert_eq!(lines[1].spans[1].style, Style::default());
+        assert_eq!(
+            RenderMode::Compact.render(&cells, &styles).lines[0].spans[0].style,
+            Style::default()
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use crate::core::{Board, Game, Replay};
+
+use super::{
+    game::{blink_on, render_game},
+    settings::Settings,
+    viewport::Viewport,
+};
+
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind};
+use futures::StreamExt;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Line,
+    widgets::{Block, Widget},
+    DefaultTerminal, Frame,
+};
+use std::io;
+use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
+
+const FRAME: Duration = Duration::from_millis(16);
+
+pub struct ReplayTui<'a> {
+    replay: &'a Replay,
+    game: Game<'a>,
+    settings: Settings,
+    viewport: Viewport,
+    finished_at: Option<Instant>,
+    exit: bool,
+}
+
+impl<'a> ReplayTui<'a> {
+    pub fn new(board: &'a Board, replay: &'a Replay, settings: Settings) -> Self {
+        Self {
+            replay,
+            game: replay.start(board),
+            settings,
+            viewport: Viewport::new(settings.get_viewport_margin()),
+            finished_at: None,
+            exit: false,
+        }
+    }
+
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let mut events = EventStream::new();
+        let mut tick = interval(self.settings.get_tick());
+        let mut frame = interval(FRAME);
+        frame.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        while !self.exit {
+            tokio::select! {
+                _ = tick.tick() => {
+                    if self.finished_at.is_none() && !self.replay.step(&mut self.game) {
+                        self.finished_at = Some(Instant::now());
+                    }
+                }
+                _ = frame.tick() => {
+                    terminal.draw(|frame| self.draw(frame))?;
+                }
+                Some(event) = events.next() => self.handle_event(event?),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+    }
+
+    fn key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => self.exit = true,
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                self.game = self.replay.start(self.game.get_board());
+                self.finished_at = None;
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => self.settings.next_render_mode(),
+            _ => {}
+        }
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        if let Event::Key(key_event) = event {
+            if key_event.kind == KeyEventKind::Press {
+                self.key_event(key_event)
+            }
+        }
+    }
+}
+
+impl Widget for &mut ReplayTui<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = match (self.finished_at, self.game.get_death()) {
+            (Some(_), Some(death)) => format!(
+                "Replay finished, score: {}. {}",
+                self.game.get_score(),
+                death.describe()
+            ),
+            (Some(_), None) => format!("Replay finished, score: {}", self.game.get_score()),
+            (None, _) => format!("Replay, score: {}", self.game.get_score()),
+        };
+        let instructions = Line::from("Use r/R to restart, v/V to change view, q/Q to go back.");
+
+        let block = Block::new()
+            .title(Line::from(title).centered())
+            .title_bottom(instructions.centered());
+        let board_area = block.inner(area);
+        block.render(area, buf);
+
+        render_game(
+            &self.game,
+            &mut self.viewport,
+            self.settings.get_render_mode(),
+            self.finished_at.is_some_and(blink_on),
+            &[],
+            board_area,
+            buf,
+        );
+    }
+}
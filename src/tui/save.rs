@@ -0,0 +1,80 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::lineup::Lineup;
+use crate::core::SavedGame;
+
+const JSON_FILE_PATH: &str = "./src/save.json";
+
+/// The game left with "Save and quit", kept until it is continued.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Save {
+    game: SavedGame,
+    lineup: Lineup,
+    elapsed: Duration,
+    assisted: bool,
+    /// The campaign level the game is, if it is one.
+    level: Option<usize>,
+}
+
+impl Save {
+    pub fn new(
+        game: SavedGame,
+        lineup: Lineup,
+        elapsed: Duration,
+        assisted: bool,
+        level: Option<usize>,
+    ) -> Self {
+        Self {
+            game,
+            lineup,
+            elapsed,
+            assisted,
+            level,
+        }
+    }
+
+    /// The saved game, if there is one that can still be read.
+    pub fn load() -> Option<Self> {
+        let file = File::open(JSON_FILE_PATH).ok()?;
+
+        serde_json::from_reader(BufReader::new(file)).ok()
+    }
+
+    pub fn write(&self) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(JSON_FILE_PATH)?);
+
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Forgets the saved game once it is continued, so that it is only
+    /// played once.
+    pub fn remove() {
+        let _ = fs::remove_file(JSON_FILE_PATH);
+    }
+
+    pub fn get_game(&self) -> &SavedGame {
+        &self.game
+    }
+
+    pub fn get_lineup(&self) -> Lineup {
+        self.lineup
+    }
+
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn is_assisted(&self) -> bool {
+        self.assisted
+    }
+
+    pub fn get_level(&self) -> Option<usize> {
+        self.level
+    }
+}
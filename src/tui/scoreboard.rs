@@ -1,6 +1,6 @@
 use std::io;
 
-use crate::core::Scoreboard;
+use crate::core::{Record, Scoreboard};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 use itertools::Itertools;
 use ratatui::{
@@ -84,7 +84,7 @@ impl ScoreboardTui {
         self.state.select_previous();
     }
 
-    fn selected_board(&self) -> Vec<u16> {
+    fn selected_board(&self) -> Vec<Record> {
         let index = self.state.selected().unwrap();
         let board_name = &self.board_names[index];
         self.scoreboard.get(board_name).unwrap().clone()
@@ -129,7 +129,14 @@ impl ScoreboardTui {
             .selected_board()
             .iter()
             .enumerate()
-            .map(|(index, score)| format!("{}: {}", index + 1, score))
+            .map(|(index, record)| {
+                format!(
+                    "{}: {} {}",
+                    index + 1,
+                    record.get_name(),
+                    record.get_score()
+                )
+            })
             .join("\n");
 
         let block = Block::new()
@@ -1,13 +1,18 @@
+use super::{
+    lineup::Lineup,
+    render_mode::{self, RenderMode},
+    save::Save,
+    viewport::Viewport,
+};
 use crate::core::{Board, Boards};
 
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use itertools::Itertools;
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Rect},
     style::{Modifier, Style, Stylize},
     symbols::border,
-    text::Line,
+    text::{Line, Text},
     widgets::{
         Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
         Widget,
@@ -17,10 +22,13 @@ use ratatui::{
 use std::io;
 
 pub enum SelectBoardTuiResult {
-    Board(Board),
+    Board(Board, Lineup),
+    /// The saved game and the board it was played on.
+    Continue(Board, Box<Save>),
     Exit,
     CreateBoard,
     ScoreBoards,
+    Campaign,
 }
 
 pub struct SelectBoardTui {
@@ -28,21 +36,36 @@ pub struct SelectBoardTui {
     selected: bool,
     create_board: bool,
     show_scoreboards: bool,
+    show_campaign: bool,
     boards: Boards,
     board_names: Vec<String>,
     state: ListState,
+    viewport: Viewport,
+    render_mode: RenderMode,
+    lineup: Lineup,
+    /// The saved game, listed first, and the index of its board.
+    save: Option<(usize, Save)>,
+    /// Why the saved game could not go on, if it could not.
+    message: Option<String>,
 }
 
 impl Default for SelectBoardTui {
     fn default() -> Self {
-        Self::new()
+        Self::new(None)
     }
 }
 
 impl SelectBoardTui {
-    pub fn new() -> Self {
+    /// The board list, showing `message` about the saved game.
+    pub fn new(message: Option<String>) -> Self {
         let boards = Boards::new();
         let board_names = boards.get_names();
+        let save = Save::load().and_then(|save| {
+            let board = board_names
+                .iter()
+                .position(|name| name == save.get_game().get_board_name())?;
+            Some((board, save))
+        });
 
         let mut state = ListState::default();
         state.select_first();
@@ -52,14 +75,25 @@ impl SelectBoardTui {
             selected: false,
             create_board: false,
             show_scoreboards: false,
+            show_campaign: false,
             state,
             boards,
             board_names,
+            viewport: Viewport::centered(),
+            render_mode: RenderMode::default(),
+            lineup: Lineup::default(),
+            save,
+            message,
         }
     }
 
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<SelectBoardTuiResult> {
-        while !(self.exit || self.selected || self.create_board || self.show_scoreboards) {
+        while !(self.exit
+            || self.selected
+            || self.create_board
+            || self.show_scoreboards
+            || self.show_campaign)
+        {
             terminal.draw(|frame| self.draw(frame))?;
 
             self.handle_events()?;
@@ -71,8 +105,16 @@ impl SelectBoardTui {
             SelectBoardTuiResult::CreateBoard
         } else if self.show_scoreboards {
             SelectBoardTuiResult::ScoreBoards
+        } else if self.show_campaign {
+            SelectBoardTuiResult::Campaign
+        } else if self.is_continue_selected() {
+            let (_, save) = self
+                .save
+                .take()
+                .expect("continue is only listed with a save");
+            SelectBoardTuiResult::Continue(self.selected_board(), Box::new(save))
         } else {
-            SelectBoardTuiResult::Board(self.selected_board())
+            SelectBoardTuiResult::Board(self.selected_board(), self.lineup)
         };
 
         Ok(select_board_tui_result)
@@ -85,9 +127,16 @@ impl SelectBoardTui {
     fn key_event(&mut self, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Enter => self.selected = true,
-            KeyCode::Char('q') => self.exit = true,
-            KeyCode::Char('c') => self.create_board = true,
-            KeyCode::Char('s') => self.show_scoreboards = true,
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.exit = true,
+            KeyCode::Char('c') | KeyCode::Char('C') => self.create_board = true,
+            KeyCode::Char('s') | KeyCode::Char('S') => self.show_scoreboards = true,
+            KeyCode::Char('a') | KeyCode::Char('A') => self.show_campaign = true,
+            KeyCode::Char('v') | KeyCode::Char('V') => self.render_mode = self.render_mode.next(),
+            KeyCode::Char('p') | KeyCode::Char('P') => self.lineup.next_humans(),
+            KeyCode::Char('b') | KeyCode::Char('B') => self.lineup.next_bots(),
+            KeyCode::Char('d') | KeyCode::Char('D') => self.lineup.next_difficulty(),
+            KeyCode::Char('m') | KeyCode::Char('M') => self.lineup.next_mode(),
+            KeyCode::Char('r') | KeyCode::Char('R') => self.lineup.toggle_practice(),
             KeyCode::Char('j') | KeyCode::Down => self.select_next(),
             KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
             _ => {}
@@ -112,21 +161,34 @@ impl SelectBoardTui {
         self.state.select_previous();
     }
 
+    fn is_continue_selected(&self) -> bool {
+        self.save.is_some() && self.state.selected() == Some(0)
+    }
+
     fn selected_board(&self) -> Board {
-        let index = self.state.selected().unwrap();
+        let selected = self.state.selected().unwrap();
+        let index = match &self.save {
+            Some((board, _)) if selected == 0 => *board,
+            Some(_) => selected - 1,
+            None => selected,
+        };
         let border = self.boards.get(index).unwrap().clone();
         border
     }
 
-    fn render_header(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Select Board")
-            .bold()
+    fn render_header(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![Line::from("Select Board").bold()];
+        if let Some(message) = &self.message {
+            lines.push(Line::from(message.as_str()).red());
+        }
+
+        Paragraph::new(Text::from(lines))
             .centered()
             .render(area, buf);
     }
 
     fn render_footer(area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Use ↓↑ to move, c/C to go create board, s/S to go show scoreboards, ⮡ to go play selected board, q/Q to quit game.")
+        Paragraph::new("Use ↓↑ to move, c/C to go create board, s/S to go show scoreboards, a/A to play the campaign, v/V to change preview, p/P to change players, b/B to add bots, d/D to change bot difficulty, m/M to change mode, r/R to toggle practice, ⮡ to go play selected board, q/Q to quit game.")
             .centered()
             .render(area, buf);
     }
@@ -134,13 +196,28 @@ impl SelectBoardTui {
     fn render_list_of_name(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::new()
             .title(Line::raw(" Board Names ").centered())
+            .title_bottom(Line::raw(format!(" {} ", self.lineup.describe())).centered())
+            .title_bottom(
+                Line::raw(match self.lineup.is_practice() {
+                    true => format!(" {}, practice ", self.lineup.get_mode().get_name()),
+                    false => format!(" {} ", self.lineup.get_mode().get_name()),
+                })
+                .left_aligned(),
+            )
             .borders(Borders::ALL)
             .border_set(border::ROUNDED);
 
         let items: Vec<_> = self
-            .board_names
+            .save
             .iter()
-            .map(|todo_item| ListItem::from(todo_item.to_string()))
+            .map(|(_, save)| {
+                ListItem::from(format!("Continue on {}", save.get_game().get_board_name())).green()
+            })
+            .chain(
+                self.board_names
+                    .iter()
+                    .map(|todo_item| ListItem::from(todo_item.to_string())),
+            )
             .collect();
 
         let list = List::new(items)
@@ -152,23 +229,26 @@ impl SelectBoardTui {
         StatefulWidget::render(list, area, buf, &mut self.state);
     }
 
-    fn render_selected_item(&self, area: Rect, buf: &mut Buffer) {
-        let selected_board = self
-            .selected_board()
-            .get_table()
-            .iter()
-            .map(|row| row.join(""))
-            .join("\n");
+    fn render_selected_item(&mut self, area: Rect, buf: &mut Buffer) {
+        let selected_board = self.selected_board().get_table();
+        let center = selected_board.len() / 2;
 
         let block = Block::new()
             .title(Line::raw(" Selected Board ").centered())
+            .title_bottom(Line::raw(format!(" {} ", self.render_mode.get_name())).right_aligned())
             .borders(Borders::ALL)
             .border_set(border::ROUNDED);
+        let board_area = block.inner(area);
+        block.render(area, buf);
 
-        Paragraph::new(selected_board)
-            .block(block)
-            .alignment(Alignment::Center)
-            .render(area, buf);
+        self.viewport.render(
+            &selected_board,
+            &render_mode::unstyled(&selected_board),
+            (center, center),
+            self.render_mode,
+            board_area,
+            buf,
+        );
     }
 }
 
@@ -185,7 +265,7 @@ impl Widget for &mut SelectBoardTui {
             Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
                 .areas(main_area);
 
-        SelectBoardTui::render_header(header_area, buf);
+        self.render_header(header_area, buf);
         SelectBoardTui::render_footer(footer_area, buf);
         self.render_list_of_name(list_area, buf);
         self.render_selected_item(item_area, buf);
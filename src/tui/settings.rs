@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use super::render_mode::RenderMode;
+
+const TICK_STEP_MILLIS: u64 = 10;
+const MIN_TICK_MILLIS: u64 = 30;
+const MAX_TICK_MILLIS: u64 = 300;
+const MAX_VIEWPORT_MARGIN: usize = 10;
+
+/// Player preferences that outlive a single game.
+#[derive(Debug, Clone, Copy)]
+pub struct Settings {
+    tick_millis: u64,
+    render_mode: RenderMode,
+    viewport_margin: usize,
+    path_hint: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            tick_millis: 90,
+            render_mode: RenderMode::default(),
+            viewport_margin: 4,
+            path_hint: false,
+        }
+    }
+}
+
+impl Settings {
+    pub fn get_tick(&self) -> Duration {
+        Duration::from_millis(self.tick_millis)
+    }
+
+    pub fn faster(&mut self) {
+        self.tick_millis = (self.tick_millis - TICK_STEP_MILLIS).max(MIN_TICK_MILLIS);
+    }
+
+    pub fn slower(&mut self) {
+        self.tick_millis = (self.tick_millis + TICK_STEP_MILLIS).min(MAX_TICK_MILLIS);
+    }
+
+    pub fn get_render_mode(&self) -> RenderMode {
+        self.render_mode
+    }
+
+    pub fn next_render_mode(&mut self) {
+        self.render_mode = self.render_mode.next();
+    }
+
+    pub fn get_viewport_margin(&self) -> usize {
+        self.viewport_margin
+    }
+
+    pub fn wider_margin(&mut self) {
+        self.viewport_margin = (self.viewport_margin + 1).min(MAX_VIEWPORT_MARGIN);
+    }
+
+    pub fn narrower_margin(&mut self) {
+        self.viewport_margin = self.viewport_margin.saturating_sub(1);
+    }
+
+    /// Whether the way from the head to the food is drawn on the board.
+    pub fn has_path_hint(&self) -> bool {
+        self.path_hint
+    }
+
+    pub fn toggle_path_hint(&mut self) {
+        self.path_hint = !self.path_hint;
+    }
+}
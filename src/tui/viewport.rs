@@ -0,0 +1,161 @@
+use super::render_mode::RenderMode;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Paragraph, Widget},
+};
+
+/// A movable window over a table that is larger than the area it is drawn in.
+///
+/// The window follows a focus cell (the snake's head, the editor cursor, ...)
+/// and scrolls only when the focus gets closer than `margin` cells to an edge.
+#[derive(Debug, Clone)]
+pub struct Viewport {
+    margin: usize,
+    row: usize,
+    column: usize,
+}
+
+impl Viewport {
+    pub fn new(margin: usize) -> Self {
+        Self {
+            margin,
+            row: 0,
+            column: 0,
+        }
+    }
+
+    /// A viewport that always keeps the focus in its middle.
+    pub fn centered() -> Self {
+        Self::new(usize::MAX)
+    }
+
+    pub fn set_margin(&mut self, margin: usize) {
+        self.margin = margin;
+    }
+
+    /// The smallest `(rows, columns)` window that still keeps the margins
+    /// around the focus of a `table` sized table.
+    pub fn min_visible(&self, table: (usize, usize)) -> (usize, usize) {
+        let cells = self.margin.saturating_mul(2).saturating_add(1);
+
+        (table.0.min(cells), table.1.min(cells))
+    }
+
+    /// Scrolls the window so that `focus` stays inside it.
+    ///
+    /// All arguments are `(rows, columns)` pairs in table cells.
+    pub fn follow(
+        &mut self,
+        focus: (usize, usize),
+        table: (usize, usize),
+        visible: (usize, usize),
+    ) {
+        self.row = Self::scroll(self.row, focus.0, table.0, visible.0, self.margin);
+        self.column = Self::scroll(self.column, focus.1, table.1, visible.1, self.margin);
+    }
+
+    pub fn crop<T: Clone>(&self, table: &[Vec<T>], visible: (usize, usize)) -> Vec<Vec<T>> {
+        table
+            .iter()
+            .skip(self.row)
+            .take(visible.0)
+            .map(|row| {
+                row.iter()
+                    .skip(self.column)
+                    .take(visible.1)
+                    .cloned()
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Follows `focus`, crops `table` to the area and draws it centered with `mode`.
+    ///
+    /// `styles` has the same shape as `table` and colors the matching cells.
+    pub fn render(
+        &mut self,
+        table: &[Vec<String>],
+        styles: &[Vec<Style>],
+        focus: (usize, usize),
+        mode: RenderMode,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let size = (table.len(), table.first().map_or(0, |row| row.len()));
+        let mode = mode.resolve(size, area);
+        let visible = mode.capacity(area);
+
+        self.follow(focus, size, visible);
+
+        let text = mode.render(&self.crop(table, visible), &self.crop(styles, visible));
+
+        Paragraph::new(text).centered().render(area, buf);
+    }
+}
+
+impl Viewport {
+    fn scroll(offset: usize, focus: usize, len: usize, visible: usize, margin: usize) -> usize {
+        if len <= visible || visible == 0 {
+            return 0;
+        }
+
+        let margin = margin.min((visible - 1) / 2);
+        let mut offset = offset;
+
+        if focus < offset + margin {
+            offset = focus.saturating_sub(margin);
+        } else if focus + margin >= offset + visible {
+            offset = focus + margin + 1 - visible;
+        }
+
+        offset.min(len - visible)
+    }
+}
+
+#[cfg(test)]
+mod test_viewport {
+    use super::Viewport;
+
+    #[test]
+    fn scroll() {
+        assert_eq!(Viewport::scroll(0, 15, 10, 20, 3), 0);
+
+        assert_eq!(Viewport::scroll(0, 5, 60, 20, 3), 0);
+        assert_eq!(Viewport::scroll(0, 16, 60, 20, 3), 0);
+        assert_eq!(Viewport::scroll(0, 17, 60, 20, 3), 1);
+        assert_eq!(Viewport::scroll(10, 12, 60, 20, 3), 9);
+        assert_eq!(Viewport::scroll(30, 59, 60, 20, 3), 40);
+        assert_eq!(Viewport::scroll(40, 0, 60, 20, 3), 0);
+
+        assert_eq!(Viewport::scroll(0, 4, 60, 5, 10), 2);
+    }
+
+    #[test]
+    fn min_visible() {
+        assert_eq!(Viewport::new(4).min_visible((60, 60)), (9, 9));
+        assert_eq!(Viewport::new(4).min_visible((5, 60)), (5, 9));
+        assert_eq!(Viewport::centered().min_visible((60, 60)), (60, 60));
+    }
+
+    #[test]
+    fn follow_and_crop() {
+        let table: Vec<Vec<String>> = (0..6)
+            .map(|row| (0..6).map(|column| format!("{row}{column}")).collect())
+            .collect();
+
+        let mut viewport = Viewport::new(1);
+        viewport.follow((5, 0), (6, 6), (3, 2));
+
+        assert_eq!(
+            viewport.crop(&table, (3, 2)),
+            vec![
+                vec!["30".to_string(), "31".to_string()],
+                vec!["40".to_string(), "41".to_string()],
+                vec!["50".to_string(), "51".to_string()],
+            ]
+        );
+    }
+}
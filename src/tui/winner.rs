@@ -0,0 +1,169 @@
+use std::io;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    symbols::border,
+    text::{Line, Text},
+    widgets::{
+        Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph, StatefulWidget,
+        Widget,
+    },
+    DefaultTerminal, Frame,
+};
+
+use super::{game::player_color, lineup::Lineup, replay::ReplayTui, settings::Settings};
+use crate::core::{Board, Player, Replay};
+
+const MENU_ITEMS: [&str; 3] = ["Rematch", "Watch replay", "Back to menu"];
+
+pub enum WinnerTuiResult {
+    Rematch,
+    Menu,
+}
+
+/// The end of a multiplayer game: who won and how every player did.
+pub struct WinnerTui {
+    board: Board,
+    lineup: Lineup,
+    players: Vec<Player>,
+    winner: Option<usize>,
+    replay: Replay,
+    settings: Settings,
+    menu: ListState,
+    result: Option<WinnerTuiResult>,
+}
+
+impl WinnerTui {
+    pub fn new(
+        board: Board,
+        lineup: Lineup,
+        players: Vec<Player>,
+        winner: Option<usize>,
+        replay: Replay,
+        settings: Settings,
+    ) -> Self {
+        let mut menu = ListState::default();
+        menu.select_first();
+
+        Self {
+            board,
+            lineup,
+            players,
+            winner,
+            replay,
+            settings,
+            menu,
+            result: None,
+        }
+    }
+
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<WinnerTuiResult> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Some(result) = self.result.take() {
+                return Ok(result);
+            }
+
+            match event::read()? {
+                Event::Key(key_event)
+                    if key_event.kind == KeyEventKind::Press && self.key_event(key_event) =>
+                {
+                    ReplayTui::new(&self.board, &self.replay, self.settings)
+                        .run(terminal)
+                        .await?;
+                }
+                _ => {}
+            };
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        frame.render_widget(self, frame.area());
+    }
+
+    /// Returns whether the replay should be shown.
+    fn key_event(&mut self, key_event: KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
+                self.result = Some(WinnerTuiResult::Menu)
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.menu.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.menu.select_previous(),
+            KeyCode::Enter => match self.menu.selected() {
+                Some(0) => self.result = Some(WinnerTuiResult::Rematch),
+                Some(1) => return true,
+                Some(2) => self.result = Some(WinnerTuiResult::Menu),
+                _ => {}
+            },
+            _ => {}
+        }
+
+        false
+    }
+}
+
+impl Widget for &mut WinnerTui {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [summary_area, menu_area] = Layout::vertical([
+            Constraint::Length(self.players.len() as u16 + 3),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        let headline = match self.winner {
+            Some(winner) => Line::from(format!("{} wins!", self.lineup.get_name(winner)))
+                .fg(player_color(winner))
+                .bold(),
+            None => Line::from("It's a draw!").bold(),
+        };
+
+        let mut lines = vec![headline, Line::from("")];
+        lines.extend(self.players.iter().enumerate().map(|(index, player)| {
+            let outcome = match player.get_death() {
+                Some(death) => death.describe(),
+                None => "Survived.".to_string(),
+            };
+
+            Line::from(format!(
+                "{}: {} points. {}",
+                self.lineup.get_name(index),
+                player.get_score(),
+                outcome
+            ))
+            .fg(player_color(index))
+        }));
+
+        Paragraph::new(Text::from(lines))
+            .block(Block::new().title(Line::from("Game Over").centered()))
+            .centered()
+            .render(summary_area, buf);
+
+        let [_, menu_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(20),
+            Constraint::Fill(1),
+        ])
+        .areas(menu_area);
+
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED);
+
+        let items: Vec<_> = MENU_ITEMS
+            .iter()
+            .map(|item| ListItem::from(*item))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, menu_area, buf, &mut self.menu);
+    }
+}
@@ -0,0 +1,35 @@
+use std::process::Command;
+
+fn bench(program: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_snake_game"))
+        .args(["bench", "--games", "3", "--ticks", "300", "--budget", "200"])
+        .args(["--program", program])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn sample_bot_plays() {
+    let report = bench(env!("CARGO_BIN_EXE_sample_bot"));
+
+    assert!(report.contains("3 games of the"), "{report}");
+    assert!(!report.contains("forfeited"), "{report}");
+    assert!(!report.contains("score:    min 0 median 0"), "{report}");
+}
+
+#[test]
+fn broken_bots_forfeit() {
+    let stalling = bench(&format!("{} --stall", env!("CARGO_BIN_EXE_sample_bot")));
+    assert!(stalling.contains("    3 forfeited"), "{stalling}");
+    assert!(stalling.contains("did not answer within"), "{stalling}");
+
+    let garbage = bench(&format!("{} --garbage", env!("CARGO_BIN_EXE_sample_bot")));
+    assert!(garbage.contains("    3 forfeited"), "{garbage}");
+    assert!(garbage.contains("which is not a move"), "{garbage}");
+
+    let missing = bench("./there/is/no/such/bot");
+    assert!(missing.contains("could not be started"), "{missing}");
+}